@@ -1,9 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
 pub enum APiEnum {
     AccountBalance,
     AccountPositions,
     AccountSetLeverage,
     AccountPositionsHistory,
     AccountConfig,
+    AccountBills,
+    AccountBillsArchive,
+    AccountMaxSize,
+    AccountMaxAvailSize,
+    AccountMaxLoan,
+    AccountTradeFee,
+    AccountLeverageInfo,
+    AccountSetPositionMode,
+    AccountSetGreeks,
+    AccountSetIsolatedMode,
+    AccountMarginBalance,
+    AccountSetAutoLoan,
     TradeOrdersPending,
     TradeOrdersHistory,
     // 限速：300个/2s
@@ -21,11 +36,237 @@ pub enum APiEnum {
     // 限速规则（期权以外）：UserID + Instrument ID
     // 限速规则（只限期权）：UserID + Instrument Family
     TradeGetOrder,
-    // 限速规则：UserID + Instrument ID
+    // 限速规则（期权以外）：UserID + Instrument ID
+    // 限速规则（只限期权）：UserID + Instrument Family
     TradeAmendOrder,
+    // 限速规则（期权以外）：UserID + Instrument ID
+    // 限速规则（只限期权）：UserID + Instrument Family
+    TradeCancelOrder,
+    // 限速：300个/2s
+    // 限速规则（期权以外）：UserID + Instrument ID
+    // 限速规则（只限期权）：UserID + Instrument Family
+    TradeAmendBatchOrders,
+    // 限速规则（期权以外）：UserID + Instrument ID
+    // 限速规则（只限期权）：UserID + Instrument Family
+    TradeClosePosition,
+    TradeFills,
+    TradeFillsHistory,
+    // 限速规则（期权以外）：UserID + Instrument ID
+    // 限速规则（只限期权）：UserID + Instrument Family
+    TradeOrderAlgo,
+    TradeCancelAlgos,
+    // 限速规则（期权以外）：UserID + Instrument ID
+    // 限速规则（只限期权）：UserID + Instrument Family
+    TradeAmendAlgos,
+    TradeOrdersAlgoPending,
+    TradeOrdersAlgoHistory,
+    TradeGetAlgoOrder,
     MarketTickers,
     MarketTicker,
     MarketTrades,
     MarketBooks,
+    MarketCandles,
+    MarketHistoryCandles,
+    MarketIndexCandles,
+    MarketMarkPriceCandles,
     PublicInstruments,
-}
\ No newline at end of file
+    PublicFundingRate,
+    PublicFundingRateHistory,
+    PublicOpenInterest,
+    PublicMarkPrice,
+    PublicPriceLimit,
+    PublicEstimatedPrice,
+    PublicPositionTiers,
+    PublicInsuranceFund,
+    PublicUnderlying,
+    PublicTime,
+    SystemStatus,
+    AssetBalances,
+    AssetCurrencies,
+    // 限速规则：UserID + Currency
+    AssetTransfer,
+    AssetTransferState,
+    AssetBills,
+    AssetValuation,
+    AssetDepositAddress,
+    AssetDepositHistory,
+    AssetWithdrawal,
+    AssetCancelWithdrawal,
+    AssetWithdrawalHistory,
+}
+
+/// 限速规则的计数维度
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub enum LimitKey {
+    /// UserID，公共接口为IP
+    UserId,
+    /// UserID + Instrument ID，期权为UserID + Instrument Family
+    InstId,
+    /// UserID + Instrument Family
+    InstFamily,
+    /// UserID + Instrument Type
+    InstType,
+    /// UserID + Currency
+    Ccy,
+}
+
+/// 接口的限速规则：window_ms毫秒内最多使用capacity的权重，每次请求使用weight的权重
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct LimitRule {
+    pub weight: u32,
+    pub capacity: u32,
+    pub window_ms: i64,
+    pub key: LimitKey,
+}
+
+impl LimitRule {
+    pub const fn new(capacity: u32, window_ms: i64, key: LimitKey) -> Self {
+        Self {
+            weight: 1,
+            capacity,
+            window_ms,
+            key,
+        }
+    }
+}
+
+impl APiEnum {
+    /// okx文档中的默认限速规则
+    pub fn rule(&self) -> LimitRule {
+        use LimitKey::*;
+        match self {
+            // 限速：10次/2s
+            APiEnum::AccountBalance => LimitRule::new(10, 2000, UserId),
+            // 限速：10次/2s
+            APiEnum::AccountPositions => LimitRule::new(10, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::AccountSetLeverage => LimitRule::new(20, 2000, UserId),
+            // 限速：1次/10s
+            APiEnum::AccountPositionsHistory => LimitRule::new(1, 10000, UserId),
+            // 限速：5次/2s
+            APiEnum::AccountConfig => LimitRule::new(5, 2000, UserId),
+            // 限速：5次/s
+            APiEnum::AccountBills => LimitRule::new(5, 1000, UserId),
+            // 限速：5次/2s
+            APiEnum::AccountBillsArchive => LimitRule::new(5, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::AccountMaxSize => LimitRule::new(20, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::AccountMaxAvailSize => LimitRule::new(20, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::AccountMaxLoan => LimitRule::new(20, 2000, UserId),
+            // 限速：5次/2s
+            APiEnum::AccountTradeFee => LimitRule::new(5, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::AccountLeverageInfo => LimitRule::new(20, 2000, UserId),
+            // 限速：5次/2s
+            APiEnum::AccountSetPositionMode => LimitRule::new(5, 2000, UserId),
+            // 限速：5次/2s
+            APiEnum::AccountSetGreeks => LimitRule::new(5, 2000, UserId),
+            // 限速：5次/2s
+            APiEnum::AccountSetIsolatedMode => LimitRule::new(5, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::AccountMarginBalance => LimitRule::new(20, 2000, UserId),
+            // 限速：5次/2s
+            APiEnum::AccountSetAutoLoan => LimitRule::new(5, 2000, UserId),
+            // 限速：60次/2s
+            APiEnum::TradeOrdersPending => LimitRule::new(60, 2000, UserId),
+            // 限速：40次/2s
+            APiEnum::TradeOrdersHistory => LimitRule::new(40, 2000, UserId),
+            // 限速：300个/2s
+            APiEnum::TradeCancelBatchOrders => LimitRule::new(300, 2000, InstId),
+            // 限速：60次/2s
+            APiEnum::TradePlaceOrder => LimitRule::new(60, 2000, InstId),
+            // 限速：300个/2s
+            APiEnum::TradePlaceBatchOrders => LimitRule::new(300, 2000, InstId),
+            // 限速：60次/2s
+            APiEnum::TradeGetOrder => LimitRule::new(60, 2000, InstId),
+            // 限速：60次/2s
+            APiEnum::TradeAmendOrder => LimitRule::new(60, 2000, InstId),
+            // 限速：60次/2s
+            APiEnum::TradeCancelOrder => LimitRule::new(60, 2000, InstId),
+            // 限速：300个/2s
+            APiEnum::TradeAmendBatchOrders => LimitRule::new(300, 2000, InstId),
+            // 限速：20次/2s
+            APiEnum::TradeClosePosition => LimitRule::new(20, 2000, InstId),
+            // 限速：60次/2s
+            APiEnum::TradeFills => LimitRule::new(60, 2000, UserId),
+            // 限速：10次/2s
+            APiEnum::TradeFillsHistory => LimitRule::new(10, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::TradeOrderAlgo => LimitRule::new(20, 2000, InstId),
+            // 限速：20次/2s
+            APiEnum::TradeCancelAlgos => LimitRule::new(20, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::TradeAmendAlgos => LimitRule::new(20, 2000, InstId),
+            // 限速：20次/2s
+            APiEnum::TradeOrdersAlgoPending => LimitRule::new(20, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::TradeOrdersAlgoHistory => LimitRule::new(20, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::TradeGetAlgoOrder => LimitRule::new(20, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::MarketTickers => LimitRule::new(20, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::MarketTicker => LimitRule::new(20, 2000, UserId),
+            // 限速：100次/2s
+            APiEnum::MarketTrades => LimitRule::new(100, 2000, UserId),
+            // 限速：40次/2s
+            APiEnum::MarketBooks => LimitRule::new(40, 2000, UserId),
+            // 限速：40次/2s
+            APiEnum::MarketCandles => LimitRule::new(40, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::MarketHistoryCandles => LimitRule::new(20, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::MarketIndexCandles => LimitRule::new(20, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::MarketMarkPriceCandles => LimitRule::new(20, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::PublicInstruments => LimitRule::new(20, 2000, InstType),
+            // 限速：20次/2s
+            APiEnum::PublicFundingRate => LimitRule::new(20, 2000, InstId),
+            // 限速：10次/2s
+            APiEnum::PublicFundingRateHistory => LimitRule::new(10, 2000, InstId),
+            // 限速：20次/2s
+            APiEnum::PublicOpenInterest => LimitRule::new(20, 2000, InstId),
+            // 限速：10次/2s
+            APiEnum::PublicMarkPrice => LimitRule::new(10, 2000, InstId),
+            // 限速：20次/2s
+            APiEnum::PublicPriceLimit => LimitRule::new(20, 2000, UserId),
+            // 限速：10次/2s
+            APiEnum::PublicEstimatedPrice => LimitRule::new(10, 2000, InstId),
+            // 限速：10次/2s
+            APiEnum::PublicPositionTiers => LimitRule::new(10, 2000, UserId),
+            // 限速：10次/2s
+            APiEnum::PublicInsuranceFund => LimitRule::new(10, 2000, UserId),
+            // 限速：20次/2s
+            APiEnum::PublicUnderlying => LimitRule::new(20, 2000, UserId),
+            // 限速：10次/2s
+            APiEnum::PublicTime => LimitRule::new(10, 2000, UserId),
+            // 限速：1次/5s
+            APiEnum::SystemStatus => LimitRule::new(1, 5000, UserId),
+            // 限速：6次/s
+            APiEnum::AssetBalances => LimitRule::new(6, 1000, UserId),
+            // 限速：6次/s
+            APiEnum::AssetCurrencies => LimitRule::new(6, 1000, UserId),
+            // 限速：2次/s
+            APiEnum::AssetTransfer => LimitRule::new(2, 1000, Ccy),
+            // 限速：10次/s
+            APiEnum::AssetTransferState => LimitRule::new(10, 1000, UserId),
+            // 限速：6次/s
+            APiEnum::AssetBills => LimitRule::new(6, 1000, UserId),
+            // 限速：1次/2s
+            APiEnum::AssetValuation => LimitRule::new(1, 2000, UserId),
+            // 限速：6次/s
+            APiEnum::AssetDepositAddress => LimitRule::new(6, 1000, UserId),
+            // 限速：6次/s
+            APiEnum::AssetDepositHistory => LimitRule::new(6, 1000, UserId),
+            // 限速：6次/s
+            APiEnum::AssetWithdrawal => LimitRule::new(6, 1000, UserId),
+            // 限速：6次/s
+            APiEnum::AssetCancelWithdrawal => LimitRule::new(6, 1000, UserId),
+            // 限速：6次/s
+            APiEnum::AssetWithdrawalHistory => LimitRule::new(6, 1000, UserId),
+        }
+    }
+}
@@ -1,29 +1,96 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
 use std::sync::Arc;
 use serde::Deserialize;
-use crate::utils::request_limit::LimitMgr;
+use crate::restful::{HttpTransport, ReqwestTransport, RetryPolicy};
+use crate::{APiEnum, InstType, InstrumentRegistry, LimitRule};
+use crate::okx_error::Result;
+use crate::utils::request_limit::{LimitMgr, LimitStatus, RateLimitMode, RateLimitStore};
+use crate::utils::time_sync::TimeSync;
 use crate::websocket::{AccountWebsocket, PublicWebsocket};
 
 #[derive(Debug, Clone)]
 pub struct OkxPublicClient {
     pub base_config: OkxConfig,
     limit_mgr: Arc<LimitMgr>,
+    transport: Arc<dyn HttpTransport>,
+    retry_policy: RetryPolicy,
+    time_sync: Arc<TimeSync>,
 }
 
 impl OkxPublicClient {
+    /// http配置无效时panic，需要处理错误时使用try_new
     pub fn new(base_config: OkxConfig) -> Self {
+        let transport = default_transport(&base_config.http);
+        Self::with_transport(base_config, Arc::new(transport))
+    }
+
+    /// 按base_config.http创建http客户端，配置无效时返回错误
+    pub fn try_new(base_config: OkxConfig) -> Result<Self> {
+        let transport = ReqwestTransport::from_config(&base_config.http)?;
+        Ok(Self::with_transport(base_config, Arc::new(transport)))
+    }
+
+    /// 使用自定义的传输层创建客户端，如测试时使用MockTransport
+    pub fn with_transport(base_config: OkxConfig, transport: Arc<dyn HttpTransport>) -> Self {
         Self{
+            limit_mgr: Arc::new(base_config.create_limit_mgr()),
             base_config,
-            limit_mgr: Arc::new(LimitMgr::new()),
+            transport,
+            retry_policy: RetryPolicy::none(),
+            time_sync: Arc::new(TimeSync::new()),
         }
     }
 
+    /// 设置重试策略，默认不重试
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// 使用共享的时钟偏差，如OkxPublicClient::start_time_sync同步后的time_sync()
+    pub fn with_time_sync(mut self, time_sync: Arc<TimeSync>) -> Self {
+        self.time_sync = time_sync;
+        self
+    }
+
+    /// 设置超出本地限速时的处理方式，默认立即返回RateLimit错误
+    pub fn with_rate_limit_mode(self, mode: RateLimitMode) -> Self {
+        self.limit_mgr.set_mode(mode);
+        self
+    }
+
     pub async fn start_websocket(&self) -> Arc<PublicWebsocket> {
         PublicWebsocket::start(&self.base_config.pub_websocket_domain).await
     }
 
+    /// 使用共享的限速存储，如MemoryLimitStore::shared(uid)或多进程共用的FileLimitStore
+    pub fn with_rate_limit_store(self, store: Arc<dyn RateLimitStore>) -> Self {
+        self.limit_mgr.set_store(store);
+        self
+    }
+
+    /// 各接口限速规则的剩余额度
+    pub fn rate_limit_status(&self) -> Result<Vec<LimitStatus>> {
+        self.limit_mgr.status()
+    }
+
     pub(crate) fn limit_mgr(&self) -> &LimitMgr {
         &self.limit_mgr
     }
+
+    pub(crate) fn transport(&self) -> &dyn HttpTransport {
+        self.transport.as_ref()
+    }
+
+    pub(crate) fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// 签名时使用的时钟偏差
+    pub fn time_sync(&self) -> &Arc<TimeSync> {
+        &self.time_sync
+    }
 }
 
 #[derive(Debug)]
@@ -33,31 +100,147 @@ pub struct OkxAccountClient {
     pub passphrase: String,
     pub base_config: OkxConfig,
     limit_mgr: LimitMgr,
+    transport: Arc<dyn HttpTransport>,
+    retry_policy: RetryPolicy,
+    time_sync: Arc<TimeSync>,
+    instrument_registry: Option<InstrumentRegistry>,
+    withdrawal_enabled: bool,
+    /// 第一次提币时查询的API key是否有withdraw权限
+    withdrawal_perm: tokio::sync::OnceCell<bool>,
 }
 
 impl OkxAccountClient {
+    /// http配置无效时panic，需要处理错误时使用try_new
     pub fn new(
         base_config: OkxConfig,
         api_key: impl Into<String>,
         secret_key: impl Into<String>,
         passphrase: impl Into<String>,
+    ) -> Self {
+        let transport = default_transport(&base_config.http);
+        Self::with_transport(base_config, api_key, secret_key, passphrase, Arc::new(transport))
+    }
+
+    /// 按base_config.http创建http客户端，配置无效时返回错误
+    pub fn try_new(
+        base_config: OkxConfig,
+        api_key: impl Into<String>,
+        secret_key: impl Into<String>,
+        passphrase: impl Into<String>,
+    ) -> Result<Self> {
+        let transport = ReqwestTransport::from_config(&base_config.http)?;
+        Ok(Self::with_transport(base_config, api_key, secret_key, passphrase, Arc::new(transport)))
+    }
+
+    /// 使用自定义的传输层创建客户端，如测试时使用MockTransport
+    pub fn with_transport(
+        base_config: OkxConfig,
+        api_key: impl Into<String>,
+        secret_key: impl Into<String>,
+        passphrase: impl Into<String>,
+        transport: Arc<dyn HttpTransport>,
     ) -> Self {
         OkxAccountClient {
+            limit_mgr: base_config.create_limit_mgr(),
             base_config,
             api_key: api_key.into(),
             secret_key: secret_key.into(),
             passphrase: passphrase.into(),
-            limit_mgr: LimitMgr::new(),
+            transport,
+            retry_policy: RetryPolicy::none(),
+            time_sync: Arc::new(TimeSync::new()),
+            instrument_registry: None,
+            withdrawal_enabled: false,
+            withdrawal_perm: tokio::sync::OnceCell::new(),
         }
     }
 
+    /// 设置重试策略，默认不重试
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// 使用共享的时钟偏差，如OkxPublicClient::start_time_sync同步后的time_sync()
+    pub fn with_time_sync(mut self, time_sync: Arc<TimeSync>) -> Self {
+        self.time_sync = time_sync;
+        self
+    }
+
+    /// 设置超出本地限速时的处理方式，默认立即返回RateLimit错误
+    pub fn with_rate_limit_mode(self, mode: RateLimitMode) -> Self {
+        self.limit_mgr.set_mode(mode);
+        self
+    }
+
+    /// 下单前使用registry检查产品状态、精度和数量限制，未加载的产品会被拒绝
+    pub fn with_instrument_registry(mut self, registry: InstrumentRegistry) -> Self {
+        self.instrument_registry = Some(registry);
+        self
+    }
+
+    /// 允许提币和撤销提币，默认关闭
+    /// 开启后第一次提币前会检查API key是否有withdraw权限，检查结果会被缓存
+    pub fn with_withdrawal_enabled(mut self) -> Self {
+        self.withdrawal_enabled = true;
+        self
+    }
+
     pub async fn start_websocket(&self) -> Arc<AccountWebsocket> {
-        AccountWebsocket::start(&self.api_key, &self.secret_key, &self.passphrase, &self.base_config.private_websocket_domain).await
+        AccountWebsocket::start_with_time_sync(&self.api_key, &self.secret_key, &self.passphrase, &self.base_config.private_websocket_domain, self.time_sync.clone()).await
+    }
+
+    /// 使用共享的限速存储，如MemoryLimitStore::shared(uid)或多进程共用的FileLimitStore
+    pub fn with_rate_limit_store(self, store: Arc<dyn RateLimitStore>) -> Self {
+        self.limit_mgr.set_store(store);
+        self
+    }
+
+    /// 各接口限速规则的剩余额度
+    pub fn rate_limit_status(&self) -> Result<Vec<LimitStatus>> {
+        self.limit_mgr.status()
     }
 
     pub(crate) fn limit_mgr(&self) -> &LimitMgr {
         &self.limit_mgr
     }
+
+    pub(crate) fn transport(&self) -> &dyn HttpTransport {
+        self.transport.as_ref()
+    }
+
+    pub(crate) fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// 签名时使用的时钟偏差
+    pub fn time_sync(&self) -> &Arc<TimeSync> {
+        &self.time_sync
+    }
+
+    pub(crate) fn instrument_registry(&self) -> Option<&InstrumentRegistry> {
+        self.instrument_registry.as_ref()
+    }
+
+    /// 交易类接口的限速：期权的产品族优先从产品缓存获取，缓存中没有时再按产品id解析
+    pub(crate) async fn check_instrument_limit(&self, api: APiEnum, inst_id: &str, count: u32) -> Result<()> {
+        let instrument = self.instrument_registry.as_ref().and_then(|registry| registry.get(inst_id));
+        match instrument {
+            Some(instrument) if instrument.inst_type == InstType::Option && !instrument.inst_family.is_empty() => {
+                self.limit_mgr.check_limit_with_inst_family(api, &instrument.inst_family, count).await
+            }
+            Some(_) => self.limit_mgr.check_limit_with_inst_id(api, inst_id, count).await,
+            None => self.limit_mgr.check_limit_with_instrument(api, inst_id, count).await,
+        }
+    }
+
+    pub(crate) fn withdrawal_enabled(&self) -> bool {
+        self.withdrawal_enabled
+    }
+
+    pub(crate) fn withdrawal_perm(&self) -> &tokio::sync::OnceCell<bool> {
+        &self.withdrawal_perm
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -67,7 +250,53 @@ pub struct OkxConfig {
     pub pub_websocket_domain: String,
     pub private_websocket_domain: String,
     pub business_domain: String,
-} 
+    /// rest请求的http连接配置
+    #[serde(default)]
+    pub http: HttpConfig,
+    /// 覆盖接口的默认限速规则，如okx调整了限速或vip等级有更高的限速
+    #[serde(default)]
+    pub rate_limits: HashMap<APiEnum, LimitRule>,
+}
+
+/// rest请求的http连接配置，所有接口共用同一个连接池
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HttpConfig {
+    /// 建立连接的超时时间，单位：毫秒
+    pub connect_timeout_ms: Option<u64>,
+    /// 整个请求的超时时间，单位：毫秒
+    pub request_timeout_ms: Option<u64>,
+    /// tcp keep-alive间隔，单位：毫秒
+    pub tcp_keepalive_ms: Option<u64>,
+    /// 连接池中空闲连接的保留时间，单位：毫秒
+    pub pool_idle_timeout_ms: Option<u64>,
+    /// 每个域名最多保留的空闲连接数，None表示不限制
+    pub pool_max_idle_per_host: Option<usize>,
+    /// 是否直接使用http2协议
+    pub http2_prior_knowledge: bool,
+    /// 发送请求时绑定的本地地址
+    pub local_address: Option<IpAddr>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout_ms: Some(5000),
+            request_timeout_ms: Some(10000),
+            tcp_keepalive_ms: Some(60000),
+            pool_idle_timeout_ms: Some(90000),
+            pool_max_idle_per_host: None,
+            http2_prior_knowledge: false,
+            local_address: None,
+        }
+    }
+}
+
+/// 按配置创建http客户端，配置无效时panic
+fn default_transport(config: &HttpConfig) -> ReqwestTransport {
+    ReqwestTransport::from_config(config)
+        .unwrap_or_else(|err| panic!("invalid http config: {}, use try_new to handle the error", err))
+}
 
 #[allow(unused)]
 pub fn mainnet_config() -> OkxConfig{
@@ -77,6 +306,8 @@ pub fn mainnet_config() -> OkxConfig{
         pub_websocket_domain: "wss://ws.okx.com:8443/ws/v5/public".to_string(),
         private_websocket_domain: "wss://ws.okx.com:8443/ws/v5/private".to_string(),
         business_domain: "wss://ws.okx.com:8443/ws/v5/business".to_string(),
+        http: HttpConfig::default(),
+        rate_limits: HashMap::new(),
     }
 }
 
@@ -88,6 +319,8 @@ pub fn aws_mainnet_config()-> OkxConfig{
         pub_websocket_domain: "wss://wsaws.okx.com:8443/ws/v5/public".to_string(),
         private_websocket_domain: "wss://wsaws.okx.com:8443/ws/v5/private".to_string(),
         business_domain: "wss://wsaws.okx.com:8443/ws/v5/business".to_string(),
+        http: HttpConfig::default(),
+        rate_limits: HashMap::new(),
     }
 }
 
@@ -99,10 +332,21 @@ pub fn testnet_config() -> OkxConfig {
         pub_websocket_domain: "wss://wspap.okx.com:8443/ws/v5/public?brokerId=9999".to_string(),
         private_websocket_domain: "wss://wspap.okx.com:8443/ws/v5/private?brokerId=9999".to_string(),
         business_domain: "wss://wspap.okx.com:8443/ws/v5/business?brokerId=9999".to_string(),
+        http: HttpConfig::default(),
+        rate_limits: HashMap::new(),
     }
 }
 
 impl OkxConfig {
+    fn create_limit_mgr(&self) -> LimitMgr {
+        let limit_mgr = LimitMgr::new();
+        for (api, rule) in &self.rate_limits {
+            limit_mgr.set_rule(*api, *rule);
+        }
+        limit_mgr
+    }
+
+    /// http配置无效时panic，见OkxAccountClient::try_new
     pub fn create_account_client(self,
                                  api_key: impl Into<String>,
                                  secret_key: impl Into<String>,
@@ -110,6 +354,7 @@ impl OkxConfig {
         OkxAccountClient::new(self, api_key, secret_key, passphrase)
     }
 
+    /// http配置无效时panic，见OkxPublicClient::try_new
     pub fn create_pub_client(self) -> OkxPublicClient {
         OkxPublicClient::new(self)
     }
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use async_trait::async_trait;
+use rust_decimal::{Decimal, RoundingStrategy};
+use crate::okx_error::*;
+use crate::websocket::PublicHandler;
+use crate::{InstType, Instrument, InstrumentState, OkxPublicClient, OrderRequestInfo, OrderType, TradeSide};
+
+/// 交易产品的本地缓存，提供价格、数量的精度处理和下单前的检查
+/// 通过load加载全量产品，注册到PublicWebsocket并订阅instruments频道后会自动更新：
+/// ws.register(registry.clone()); ws.trade_symbol_change_subscribe(inst_type).await;
+#[derive(Clone, Debug, Default)]
+pub struct InstrumentRegistry {
+    instruments: Arc<RwLock<HashMap<String, Arc<Instrument>>>>,
+}
+
+impl InstrumentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 加载inst_type下的全部产品，返回加载的数量
+    /// 期权需要使用load_option_family
+    pub async fn load(&self, client: &OkxPublicClient, inst_type: InstType) -> Result<usize> {
+        let instruments = client.public_instruments(inst_type, None::<String>, None::<String>, None::<String>).await?;
+        self.update(&instruments);
+        Ok(instruments.len())
+    }
+
+    /// 加载期权交易品种下的全部产品，如 BTC-USD
+    pub async fn load_option_family(&self, client: &OkxPublicClient, inst_family: &str) -> Result<usize> {
+        let instruments = client.public_instruments(InstType::Option, None::<String>, Some(inst_family), None::<String>).await?;
+        self.update(&instruments);
+        Ok(instruments.len())
+    }
+
+    /// 新增或更新产品
+    pub fn update(&self, instruments: &[Instrument]) {
+        let mut writer = self.instruments.write().unwrap();
+        for item in instruments {
+            writer.insert(item.inst_id.clone(), Arc::new(item.clone()));
+        }
+    }
+
+    pub fn get(&self, inst_id: &str) -> Option<Arc<Instrument>> {
+        self.instruments.read().unwrap().get(inst_id).cloned()
+    }
+
+    pub fn instruments(&self, inst_type: InstType) -> Vec<Arc<Instrument>> {
+        self.instruments.read().unwrap().values()
+            .filter(|item| item.inst_type == inst_type)
+            .cloned()
+            .collect()
+    }
+
+    fn instrument(&self, inst_id: &str) -> Result<Arc<Instrument>> {
+        self.get(inst_id).ok_or_else(|| OkxError::InvalidOrder(format!("{} instrument not found", inst_id)))
+    }
+
+    /// 按tick_sz取整到最接近的价格
+    pub fn round_price(&self, inst_id: &str, px: Decimal) -> Result<Decimal> {
+        let instrument = self.instrument(inst_id)?;
+        Ok(round_to_step(px, instrument.tick_sz, RoundingStrategy::MidpointAwayFromZero))
+    }
+
+    /// 按lot_sz向下取整数量，避免超出可用余额
+    pub fn round_size(&self, inst_id: &str, sz: Decimal) -> Result<Decimal> {
+        let instrument = self.instrument(inst_id)?;
+        Ok(round_to_step(sz, instrument.lot_sz, RoundingStrategy::ToZero))
+    }
+
+    /// 合约张数转换为币的数量，如 BTC-USD-SWAP 为美元数量，BTC-USDT-SWAP 为BTC数量
+    /// 币币和币币杠杆直接返回sz
+    pub fn contracts_to_coin(&self, inst_id: &str, sz: Decimal) -> Result<Decimal> {
+        let instrument = self.instrument(inst_id)?;
+        Ok(sz * contract_value(&instrument))
+    }
+
+    /// 币的数量转换为合约张数，按lot_sz向下取整
+    pub fn coin_to_contracts(&self, inst_id: &str, amount: Decimal) -> Result<Decimal> {
+        let instrument = self.instrument(inst_id)?;
+        let value = contract_value(&instrument);
+        if value.is_zero() {
+            return Err(OkxError::InvalidOrder(format!("{} contract value is zero", inst_id)));
+        }
+        Ok(round_to_step(amount / value, instrument.lot_sz, RoundingStrategy::ToZero))
+    }
+
+    /// 检查订单是否符合产品的状态、精度和数量限制，不符合时返回InvalidOrder错误
+    pub fn check_order(&self, order: &OrderRequestInfo) -> Result<()> {
+        let instrument = self.instrument(&order.inst_id)?;
+        let invalid = |msg: String| Err(OkxError::InvalidOrder(format!("{} {}", order.inst_id, msg)));
+
+        if instrument.state != InstrumentState::Live {
+            return invalid(format!("instrument is not tradable. state:{:?}", instrument.state));
+        }
+        if let Some(px) = order.px {
+            if !is_multiple(px, instrument.tick_sz) {
+                return invalid(format!("px {} is not a multiple of tick_sz {}", px, instrument.tick_sz));
+            }
+        }
+
+        let is_market = matches!(order.order_type, OrderType::Market | OrderType::OptimalLimitIoc);
+        let is_spot = matches!(instrument.inst_type, InstType::Spot | InstType::Margin);
+        // 币币市价买单默认以计价货币下单，数量单位与min_sz、lot_sz不同
+        let in_quote_ccy = is_market && is_spot
+            && match order.tgt_ccy.as_deref() {
+                Some(tgt_ccy) => tgt_ccy == "quote_ccy",
+                None => order.side == TradeSide::Buy,
+            };
+        if !in_quote_ccy {
+            if order.sz < instrument.min_sz {
+                return invalid(format!("sz {} is less than min_sz {}", order.sz, instrument.min_sz));
+            }
+            if !is_multiple(order.sz, instrument.lot_sz) {
+                return invalid(format!("sz {} is not a multiple of lot_sz {}", order.sz, instrument.lot_sz));
+            }
+        }
+
+        // 币币市价单的max_mkt_sz单位为USDT，以交易货币下单时无法直接比较
+        let max_sz = match is_market {
+            true if is_spot && !in_quote_ccy => None,
+            true => Some(instrument.max_mkt_sz),
+            false => Some(instrument.max_lmt_sz),
+        };
+        if let Some(max_sz) = max_sz {
+            if !max_sz.is_zero() && order.sz > max_sz {
+                return invalid(format!("sz {} is greater than max size {}", order.sz, max_sz));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn contract_value(instrument: &Instrument) -> Decimal {
+    match instrument.inst_type {
+        InstType::Swap | InstType::Futures | InstType::Option => {
+            instrument.ct_val.unwrap_or(Decimal::ONE) * instrument.ct_mult.unwrap_or(Decimal::ONE)
+        }
+        _ => Decimal::ONE,
+    }
+}
+
+fn round_to_step(val: Decimal, step: Decimal, strategy: RoundingStrategy) -> Decimal {
+    if step.is_zero() {
+        return val;
+    }
+    ((val / step).round_dp_with_strategy(0, strategy) * step).normalize()
+}
+
+fn is_multiple(val: Decimal, step: Decimal) -> bool {
+    step.is_zero() || (val % step).is_zero()
+}
+
+#[async_trait]
+impl PublicHandler for InstrumentRegistry {
+    fn id(&self) -> String {
+        "instrument_registry".to_string()
+    }
+
+    async fn instrument_event(&self, events: &Vec<Instrument>) {
+        self.update(events);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use rust_decimal::Decimal;
+    use crate::{APiEnum, InstType, InstrumentRegistry, LimitKey, LimitRule, MockTransport, OkxAccountClient, OkxError, OkxPublicClient, OrderRequestInfo, PositionSide, TradeMode, TradeSide};
+
+    const INSTRUMENTS_RESP: &str = r#"{"code":"0","msg":"","data":[{"alias":"","baseCcy":"","category":"1","ctMult":"1","ctType":"linear","ctVal":"0.01",
+        "ctValCcy":"BTC","expTime":"","instFamily":"BTC-USDT","instId":"BTC-USDT-SWAP","instType":"SWAP","lever":"100","listTime":"1611916828000",
+        "lotSz":"1","maxIcebergSz":"100000000","maxLmtSz":"100000000","maxMktSz":"5000","maxStopSz":"5000","maxTriggerSz":"100000000",
+        "maxTwapSz":"100000000","minSz":"1","optType":"","quoteCcy":"","settleCcy":"USDT","state":"live","stk":"","tickSz":"0.1","uly":"BTC-USDT"}]}"#;
+
+    #[tokio::test]
+    pub async fn test_registry() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(INSTRUMENTS_RESP);
+        let pub_client = OkxPublicClient::with_transport(crate::testnet_config(), transport.clone());
+        let registry = InstrumentRegistry::new();
+        assert_eq!(registry.load(&pub_client, InstType::Swap).await.unwrap(), 1);
+
+        assert_eq!(registry.round_price("BTC-USDT-SWAP", Decimal::new(2000016, 2)).unwrap(), Decimal::new(200002, 1));
+        assert_eq!(registry.round_size("BTC-USDT-SWAP", Decimal::new(39, 1)).unwrap(), Decimal::from(3));
+        assert_eq!(registry.contracts_to_coin("BTC-USDT-SWAP", Decimal::from(3)).unwrap(), Decimal::new(3, 2));
+        assert_eq!(registry.coin_to_contracts("BTC-USDT-SWAP", Decimal::new(35, 3)).unwrap(), Decimal::from(3));
+
+        let order = |sz: Decimal, px: Decimal| OrderRequestInfo::builder("BTC-USDT-SWAP", TradeMode::Cross, TradeSide::Buy, sz)
+            .limit(px)
+            .pos_side(PositionSide::Long)
+            .build()
+            .unwrap();
+        assert!(registry.check_order(&order(Decimal::from(2), Decimal::new(200001, 1))).is_ok());
+        assert!(registry.check_order(&order(Decimal::new(15, 1), Decimal::new(200001, 1))).is_err());
+        assert!(registry.check_order(&order(Decimal::from(2), Decimal::new(2000015, 2))).is_err());
+
+        let mut instrument = registry.get("BTC-USDT-SWAP").unwrap().as_ref().clone();
+        instrument.state = crate::InstrumentState::Suspend;
+        registry.update(&[instrument]);
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport.clone())
+            .with_instrument_registry(registry.clone());
+        let result = client.trade_order(order(Decimal::from(2), Decimal::new(200001, 1))).await;
+        assert!(matches!(result, Err(OkxError::InvalidOrder(_))));
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    pub async fn test_option_limit_by_registry() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(INSTRUMENTS_RESP);
+        let pub_client = OkxPublicClient::with_transport(crate::testnet_config(), transport.clone());
+        let registry = InstrumentRegistry::new();
+        registry.load(&pub_client, InstType::Swap).await.unwrap();
+        let mut option = registry.get("BTC-USDT-SWAP").unwrap().as_ref().clone();
+        option.inst_type = InstType::Option;
+        option.inst_id = "BTC-USD-230630-30000-C".to_string();
+        option.inst_family = "BTC-USD".to_string();
+        registry.update(&[option]);
+
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport.clone())
+            .with_instrument_registry(registry.clone());
+        client.limit_mgr().set_rule(APiEnum::TradeCancelOrder, LimitRule::new(2, 2000, LimitKey::InstId));
+
+        // 期权按产品缓存中的instFamily限速，其他产品按产品id限速
+        client.check_instrument_limit(APiEnum::TradeCancelOrder, "BTC-USD-230630-30000-C", 1).await.unwrap();
+        assert_eq!(client.limit_mgr().remaining(APiEnum::TradeCancelOrder, LimitKey::InstFamily, "BTC-USD").unwrap(), 1);
+        client.check_instrument_limit(APiEnum::TradeCancelOrder, "BTC-USDT-SWAP", 1).await.unwrap();
+        assert_eq!(client.limit_mgr().remaining(APiEnum::TradeCancelOrder, LimitKey::InstId, "BTC-USDT-SWAP").unwrap(), 1);
+
+        // 产品缓存中没有的期权按产品id解析产品族
+        client.check_instrument_limit(APiEnum::TradeCancelOrder, "BTC-USD-230630-32000-P", 1).await.unwrap();
+        assert_eq!(client.limit_mgr().remaining(APiEnum::TradeCancelOrder, LimitKey::InstFamily, "BTC-USD").unwrap(), 0);
+    }
+
+    #[tokio::test]
+    pub async fn test_spot_market_size() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"alias":"","baseCcy":"DOGE","category":"1","ctMult":"","ctType":"","ctVal":"",
+            "ctValCcy":"","expTime":"","instFamily":"","instId":"DOGE-USDT","instType":"SPOT","lever":"10","listTime":"1611916828000",
+            "lotSz":"0.000001","maxIcebergSz":"9999999999","maxLmtSz":"9999999999","maxMktSz":"1000000","maxStopSz":"1000000","maxTriggerSz":"9999999999",
+            "maxTwapSz":"9999999999","minSz":"10","optType":"","quoteCcy":"USDT","settleCcy":"","state":"live","stk":"","tickSz":"0.00001","uly":""}]}"#);
+        let pub_client = OkxPublicClient::with_transport(crate::testnet_config(), transport.clone());
+        let registry = InstrumentRegistry::new();
+        registry.load(&pub_client, InstType::Spot).await.unwrap();
+
+        // 市价卖单以DOGE为单位，超过以USDT为单位的max_mkt_sz也不应被拒绝
+        let sell = OrderRequestInfo::builder("DOGE-USDT", TradeMode::Cash, TradeSide::Sell, Decimal::from(2000000))
+            .market()
+            .build()
+            .unwrap();
+        assert!(registry.check_order(&sell).is_ok());
+
+        // 市价买单以USDT为单位，按max_mkt_sz检查
+        let buy = OrderRequestInfo::builder("DOGE-USDT", TradeMode::Cash, TradeSide::Buy, Decimal::from(2000000))
+            .market()
+            .build()
+            .unwrap();
+        assert!(registry.check_order(&buy).is_err());
+    }
+}
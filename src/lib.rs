@@ -5,8 +5,12 @@ pub mod utils;
 pub mod websocket;
 mod api_enum;
 mod okx_error;
+mod instrument_registry;
 
 pub use apikey::*;
+#[allow(deprecated)]
 pub use models::*;
 pub use restful::*;
-pub use okx_error::OkxError;
\ No newline at end of file
+pub use okx_error::{OkxError, OkxErrorCode};
+pub use api_enum::{APiEnum, LimitKey, LimitRule};
+pub use instrument_registry::InstrumentRegistry;
\ No newline at end of file
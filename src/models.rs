@@ -1,17 +1,19 @@
 use serde::de;
 use serde::{Deserialize, Deserializer};
 
+#[deprecated(note = "models now use rust_decimal::Decimal, this will be removed in the next release")]
 pub fn default_perent() -> f32 {
     0.0
 }
 
+#[deprecated(note = "use okx_client::utils::from_str with rust_decimal::Decimal, this will be removed in the next release")]
 pub fn de_float_from_str<'a, D>(deserializer: D) -> Result<f32, D::Error>
 where
     D: Deserializer<'a>,
 {
     let str_val = String::deserialize(deserializer)?;
 
-    if str_val == "".to_string() {
+    if str_val.is_empty() {
         return Ok(0.0);
     }
     str_val.parse::<f32>().map_err(de::Error::custom)
@@ -1,3 +1,4 @@
+use std::fmt::{Display, Formatter};
 use std::num::ParseIntError;
 use thiserror::Error;
 
@@ -15,12 +16,240 @@ pub enum OkxError {
     DecimalError(#[from]rust_decimal::Error),
     #[error("int convert error: {0:?}")]
     ParseIntError(#[from]ParseIntError),
+    #[error("io error: {0:?}")]
+    IoError(#[from]std::io::Error),
+    #[error("invalid header value: {0:?}")]
+    InvalidHeader(#[from]http::header::InvalidHeaderValue),
+    #[error("http error. status:{status} message:{message}")]
+    HttpError{ status: u16, message: String},
+    #[error("okx response data is empty")]
+    EmptyData,
+    #[error("transport error: {0}")]
+    TransportError(String),
     #[error("websocket not connected")]
     NotConnect,
     #[error("okx response error. code:{code} message:{message}")]
-    RemoteError{ code: i32, message: String},
+    RemoteError{ code: OkxErrorCode, message: String},
     #[error("order size out of range. max 20")]
     OutOfMaxOrderSize,
     #[error("all order must have same inst id")]
     MustHaveSameInstId,
-}
\ No newline at end of file
+    #[error("invalid order: {0}")]
+    InvalidOrder(String),
+    #[error("rate limit store is busy")]
+    LimitStoreBusy,
+    #[error("withdrawal not permitted: {0}")]
+    WithdrawalNotPermitted(String),
+}
+
+impl OkxError {
+    /// okx返回的错误码，非okx返回的错误为None
+    pub fn error_code(&self) -> Option<OkxErrorCode> {
+        match self {
+            OkxError::RemoteError { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// 是否可以稍后重试
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            OkxError::RateLimit => true,
+            OkxError::ReqwestError(err) => err.is_timeout() || err.is_connect(),
+            OkxError::HttpError { status, .. } => *status == 429 || *status >= 500,
+            OkxError::RemoteError { code, .. } => code.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// 是否是限速导致的错误，包括本地限速
+    pub fn is_rate_limit(&self) -> bool {
+        match self {
+            OkxError::RateLimit => true,
+            OkxError::HttpError { status, .. } => *status == 429,
+            OkxError::RemoteError { code, .. } => code.is_rate_limit(),
+            _ => false,
+        }
+    }
+
+    /// 是否是api key或签名相关的错误
+    pub fn is_auth(&self) -> bool {
+        match self {
+            OkxError::HttpError { status, .. } => *status == 401,
+            OkxError::RemoteError { code, .. } => code.is_auth(),
+            _ => false,
+        }
+    }
+
+    /// 是否是余额不足
+    pub fn is_insufficient_funds(&self) -> bool {
+        match self {
+            OkxError::RemoteError { code, .. } => code.is_insufficient_funds(),
+            _ => false,
+        }
+    }
+}
+
+macro_rules! define_error_code {
+    ($($(#[$meta:meta])* $name:ident = $code:expr,)*) => {
+        /// okx v5接口的错误码
+        #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+        pub enum OkxErrorCode {
+            $($(#[$meta])* $name,)*
+            /// 未定义的错误码
+            Other(i32),
+        }
+
+        impl OkxErrorCode {
+            pub fn from_code(code: i32) -> Self {
+                match code {
+                    $($code => OkxErrorCode::$name,)*
+                    _ => OkxErrorCode::Other(code),
+                }
+            }
+
+            pub fn code(&self) -> i32 {
+                match self {
+                    $(OkxErrorCode::$name => $code,)*
+                    OkxErrorCode::Other(code) => *code,
+                }
+            }
+        }
+    };
+}
+
+define_error_code!(
+    /// 操作全部失败
+    OperationFailed = 1,
+    /// 批量操作部分成功
+    BulkPartialSuccess = 2,
+    /// 服务暂时不可用，请稍后重试
+    ServiceUnavailable = 50001,
+    /// json 语法错误
+    JsonSyntaxError = 50002,
+    /// 接口请求超时
+    EndpointTimeout = 50004,
+    /// 接口已下线或不可用
+    ApiOffline = 50005,
+    /// 无效的 Content_Type
+    InvalidContentType = 50006,
+    /// 账户冻结
+    AccountBlocked = 50007,
+    /// 用户不存在
+    UserNotExist = 50008,
+    /// 请求频率太高
+    RateLimitReached = 50011,
+    /// 系统繁忙，请稍后重试
+    SystemBusy = 50013,
+    /// 必填参数不能为空
+    ParameterRequired = 50014,
+    /// 系统错误，请稍后重试
+    SystemError = 50026,
+    /// 子账户请求频率太高
+    SubAccountRateLimitReached = 50061,
+    /// api key 已被冻结
+    ApiKeyFrozen = 50100,
+    /// api key 与当前环境不匹配
+    ApiKeyEnvMismatch = 50101,
+    /// 请求时间戳过期
+    TimestampExpired = 50102,
+    /// OK-ACCESS-KEY 不能为空
+    AccessKeyRequired = 50103,
+    /// OK-ACCESS-PASSPHRASE 不能为空
+    PassphraseRequired = 50104,
+    /// OK-ACCESS-PASSPHRASE 错误
+    PassphraseIncorrect = 50105,
+    /// OK-ACCESS-SIGN 不能为空
+    SignRequired = 50106,
+    /// OK-ACCESS-TIMESTAMP 不能为空
+    TimestampRequired = 50107,
+    /// 无效的ip
+    InvalidIp = 50110,
+    /// 无效的 OK-ACCESS-KEY
+    InvalidAccessKey = 50111,
+    /// 无效的 OK-ACCESS-TIMESTAMP
+    InvalidTimestamp = 50112,
+    /// 无效的签名
+    InvalidSign = 50113,
+    /// 无效的授权
+    InvalidAuthorization = 50114,
+    /// api key 没有该接口的权限
+    PermissionDenied = 50120,
+    /// 参数错误
+    ParameterError = 51000,
+    /// 产品id不存在
+    InstrumentNotExist = 51001,
+    /// 下单数量超过当前档位限制
+    OrderAmountExceedsTier = 51004,
+    /// 余额不足，下单失败
+    InsufficientBalance = 51008,
+    /// 当前账户模式不支持此操作
+    AccountModeNotSupported = 51010,
+    /// 订单id重复
+    DuplicatedOrderId = 51011,
+    /// 下单数量小于最小下单数量
+    OrderAmountTooSmall = 51020,
+    /// 保证金不足，下单失败
+    InsufficientMargin = 51119,
+    /// 下单数量必须是下单精度的整数倍
+    InvalidLotSize = 51121,
+    /// 可用余额为0
+    AvailableBalanceZero = 51127,
+    /// 余额不足
+    InsufficientBalanceAvailable = 51131,
+    /// 撤单失败，订单已成交、已撤销或不存在
+    CancelFailed = 51400,
+    /// 订单已撤销
+    OrderAlreadyCanceled = 51401,
+    /// 订单已完成
+    OrderAlreadyCompleted = 51402,
+    /// 该订单类型不支持撤单
+    OrderTypeNotCancelable = 51403,
+    /// 改单失败，订单不存在
+    AmendFailed = 51503,
+    /// 订单不存在
+    OrderNotExist = 51603,
+    /// 资金账户余额不足
+    InsufficientFundingBalance = 58350,
+);
+
+impl OkxErrorCode {
+    /// 是否可以稍后重试
+    pub fn is_retryable(&self) -> bool {
+        self.is_rate_limit() || matches!(self,
+            OkxErrorCode::ServiceUnavailable
+            | OkxErrorCode::EndpointTimeout
+            | OkxErrorCode::SystemBusy
+            | OkxErrorCode::SystemError
+            | OkxErrorCode::TimestampExpired)
+    }
+
+    pub fn is_rate_limit(&self) -> bool {
+        matches!(self, OkxErrorCode::RateLimitReached | OkxErrorCode::SubAccountRateLimitReached)
+    }
+
+    pub fn is_auth(&self) -> bool {
+        (50100..=50120).contains(&self.code())
+    }
+
+    pub fn is_insufficient_funds(&self) -> bool {
+        matches!(self,
+            OkxErrorCode::InsufficientBalance
+            | OkxErrorCode::InsufficientMargin
+            | OkxErrorCode::AvailableBalanceZero
+            | OkxErrorCode::InsufficientBalanceAvailable
+            | OkxErrorCode::InsufficientFundingBalance)
+    }
+}
+
+impl Display for OkxErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+impl From<i32> for OkxErrorCode {
+    fn from(code: i32) -> Self {
+        OkxErrorCode::from_code(code)
+    }
+}
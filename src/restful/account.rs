@@ -1,16 +1,48 @@
 use std::collections::BTreeMap;
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
 use crate::api_enum::APiEnum;
 use crate::okx_error::*;
 use crate::apikey::OkxAccountClient;
-use crate::{AccountConfig, InstType};
+use crate::{AccountConfig, InstType, TradeMode};
 use crate::restful::models::AccountBalance;
 
-use super::models::{AccountPositions, AccountPositionsHistory, AccountSetLeverage, RestApi};
+use rust_decimal::Decimal;
+use super::models::{AccountPositions, AccountPositionsHistory, AccountSetLeverage, Bill, BillSubType, BillType, GreeksType, IsoMode, IsoModeType, LeverageInfo, MarginBalance, MarginBalanceType, MaxAvailSize, MaxLoan, MaxSize, PositionMode, PositionSide, RestApi, SetAutoLoan, SetGreeks, SetIsolatedMode, SetPositionMode, TradeFee};
+
+/// 账单流水的查询条件
+/// after、before为账单ID(billId)，begin、end为Unix时间戳的毫秒数
+#[derive(Serialize, Debug, Deserialize, Default, Clone)]
+pub struct BillsFilter {
+    #[serde(rename="instType")]
+    pub inst_type: Option<InstType>,
+    #[serde(rename="instId")]
+    pub inst_id: Option<String>,
+    pub ccy: Option<String>,
+    /// 保证金模式 isolated cross
+    #[serde(rename="mgnMode")]
+    pub mgn_mode: Option<TradeMode>,
+    /// 合约类型 linear inverse，仅适用于交割/永续
+    #[serde(rename="ctType")]
+    pub ct_type: Option<String>,
+    #[serde(rename="type")]
+    pub bill_type: Option<BillType>,
+    #[serde(rename="subType")]
+    pub sub_type: Option<BillSubType>,
+    /// 请求此ID之前（更旧的数据）的分页内容
+    pub after: Option<String>,
+    /// 请求此ID之后（更新的数据）的分页内容
+    pub before: Option<String>,
+    pub begin: Option<i64>,
+    pub end: Option<i64>,
+    /// 返回结果的数量，最大为100，默认100条
+    pub limit: Option<usize>,
+}
 
 impl OkxAccountClient {
     /// 账户配置信息
     pub async fn account_config(&self) -> Result<AccountConfig>{
-        self.limit_mgr().check_limit(APiEnum::AccountConfig as u32, 1, 5, 2)?;
+        self.limit_mgr().check_limit(APiEnum::AccountConfig, 1).await?;
 
         let params: BTreeMap<String, String> = BTreeMap::new();
 
@@ -20,7 +52,7 @@ impl OkxAccountClient {
     }
 
     pub async fn account_balance(&self, ccy_list: Option<Vec<String>>) -> Result<Vec<AccountBalance>> {
-        self.limit_mgr().check_limit(APiEnum::AccountBalance as u32, 1, 10, 2)?;
+        self.limit_mgr().check_limit(APiEnum::AccountBalance, 1).await?;
         //  /api/v5/account/balance
         let mut params: BTreeMap<String, String> = BTreeMap::new();
         if let Some(val) = ccy_list {
@@ -48,7 +80,7 @@ impl OkxAccountClient {
     ) -> Result<Vec<AccountPositions>>
     {
         //  /api/v5/account/positions
-        self.limit_mgr().check_limit(APiEnum::AccountPositions as u32, 1, 10, 2)?;
+        self.limit_mgr().check_limit(APiEnum::AccountPositions, 1).await?;
         let mut params: BTreeMap<String, String> = BTreeMap::new();
 
         if let Some(inst_type) = inst_type {
@@ -83,7 +115,7 @@ impl OkxAccountClient {
     ) -> Result<AccountSetLeverage>
     {
         //  /api/v5/account/set-leverage
-        self.limit_mgr().check_limit(APiEnum::AccountSetLeverage as u32, 1, 20, 2)?;
+        self.limit_mgr().check_limit(APiEnum::AccountSetLeverage, 1).await?;
 
         let mut params: BTreeMap<String, String> = BTreeMap::new();
 
@@ -127,7 +159,7 @@ impl OkxAccountClient {
     ) -> Result<Vec<AccountPositionsHistory>>
     {
         //  /api/v5/account/positions-history
-        self.limit_mgr().check_limit(APiEnum::AccountPositionsHistory as u32, 1, 1, 10)?;
+        self.limit_mgr().check_limit(APiEnum::AccountPositionsHistory, 1).await?;
 
         let mut params: BTreeMap<String, String> = BTreeMap::new();
 
@@ -163,4 +195,405 @@ impl OkxAccountClient {
             .get::<RestApi<AccountPositionsHistory>>("/api/v5/account/positions-history", &params)
             .await?.to_result()
     }
+
+    /// 账单流水查询（近七天）
+    /// 限速：5次/s
+    /// 限速规则：UserID
+    /// GET /api/v5/account/bills
+    pub async fn account_bills(&self, filter: BillsFilter) -> Result<Vec<Bill>> {
+        self.limit_mgr().check_limit(APiEnum::AccountBills, 1).await?;
+        self.get_bills("/api/v5/account/bills", &filter).await
+    }
+
+    /// 账单流水查询（近三个月）
+    /// 限速：5次/2s
+    /// 限速规则：UserID
+    /// GET /api/v5/account/bills-archive
+    pub async fn account_bills_archive(&self, filter: BillsFilter) -> Result<Vec<Bill>> {
+        self.limit_mgr().check_limit(APiEnum::AccountBillsArchive, 1).await?;
+        self.get_bills("/api/v5/account/bills-archive", &filter).await
+    }
+
+    /// 按billId向前翻页，依次返回filter条件下的全部账单，顺序为从新到旧
+    /// archive为true时查询近三个月的数据，否则查询近七天的数据
+    /// 每页请求前会等待限速额度，请求失败时返回错误并结束
+    pub fn account_bills_stream(&self, filter: BillsFilter, archive: bool) -> impl Stream<Item = Result<Bill>> + '_ {
+        let (api, path) = match archive {
+            true => (APiEnum::AccountBillsArchive, "/api/v5/account/bills-archive"),
+            false => (APiEnum::AccountBills, "/api/v5/account/bills"),
+        };
+        let page_size = filter.limit.unwrap_or(100);
+
+        self.bill_id_stream(api, page_size, filter.after.clone(), |item: &Bill| &item.bill_id, move |after| {
+            let filter = BillsFilter { after, ..filter.clone() };
+            async move { self.get_bills(path, &filter).await }
+        })
+    }
+
+    /// 获取最大可下单数量，inst_id可以传多个，用逗号分隔，最多5个
+    /// px为委托价格，不填写时按最新成交价计算，leverage为开仓杠杆倍数，默认为当前杠杆倍数
+    /// 限速：20次/2s
+    /// 限速规则：UserID
+    /// GET /api/v5/account/max-size
+    pub async fn account_max_size(
+        &self,
+        inst_id: impl Into<String>,
+        td_mode: TradeMode,
+        ccy: Option<impl Into<String>>,
+        px: Option<Decimal>,
+        leverage: Option<Decimal>,
+    ) -> Result<Vec<MaxSize>> {
+        self.limit_mgr().check_limit(APiEnum::AccountMaxSize, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        params.insert("instId".into(), inst_id.into());
+        params.insert("tdMode".into(), td_mode.into());
+        if let Some(ccy) = ccy {
+            params.insert("ccy".into(), ccy.into());
+        }
+        if let Some(px) = px {
+            params.insert("px".into(), px.to_string());
+        }
+        if let Some(leverage) = leverage {
+            params.insert("leverage".into(), leverage.to_string());
+        }
+
+        self
+            .get::<RestApi<MaxSize>>("/api/v5/account/max-size", &params)
+            .await?.to_result()
+    }
+
+    /// 获取最大可用余额/保证金，inst_id可以传多个，用逗号分隔，最多5个
+    /// 限速：20次/2s
+    /// 限速规则：UserID
+    /// GET /api/v5/account/max-avail-size
+    pub async fn account_max_avail_size(
+        &self,
+        inst_id: impl Into<String>,
+        td_mode: TradeMode,
+        ccy: Option<impl Into<String>>,
+        reduce_only: Option<bool>,
+        px: Option<Decimal>,
+    ) -> Result<Vec<MaxAvailSize>> {
+        self.limit_mgr().check_limit(APiEnum::AccountMaxAvailSize, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        params.insert("instId".into(), inst_id.into());
+        params.insert("tdMode".into(), td_mode.into());
+        if let Some(ccy) = ccy {
+            params.insert("ccy".into(), ccy.into());
+        }
+        if let Some(reduce_only) = reduce_only {
+            params.insert("reduceOnly".into(), reduce_only.to_string());
+        }
+        if let Some(px) = px {
+            params.insert("px".into(), px.to_string());
+        }
+
+        self
+            .get::<RestApi<MaxAvailSize>>("/api/v5/account/max-avail-size", &params)
+            .await?.to_result()
+    }
+
+    /// 获取交易产品最大可借，inst_id可以传多个，用逗号分隔，最多5个
+    /// 限速：20次/2s
+    /// 限速规则：UserID
+    /// GET /api/v5/account/max-loan
+    pub async fn account_max_loan(
+        &self,
+        inst_id: impl Into<String>,
+        mgn_mode: TradeMode,
+        mgn_ccy: Option<impl Into<String>>,
+    ) -> Result<Vec<MaxLoan>> {
+        self.limit_mgr().check_limit(APiEnum::AccountMaxLoan, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        params.insert("instId".into(), inst_id.into());
+        params.insert("mgnMode".into(), mgn_mode.into());
+        if let Some(mgn_ccy) = mgn_ccy {
+            params.insert("mgnCcy".into(), mgn_ccy.into());
+        }
+
+        self
+            .get::<RestApi<MaxLoan>>("/api/v5/account/max-loan", &params)
+            .await?.to_result()
+    }
+
+    /// 获取当前账户交易手续费费率
+    /// 限速：5次/2s
+    /// 限速规则：UserID
+    /// GET /api/v5/account/trade-fee
+    pub async fn account_trade_fee(
+        &self,
+        inst_type: InstType,
+        inst_id: Option<impl Into<String>>,
+        uly: Option<impl Into<String>>,
+        inst_family: Option<impl Into<String>>,
+    ) -> Result<TradeFee> {
+        self.limit_mgr().check_limit(APiEnum::AccountTradeFee, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        params.insert("instType".into(), inst_type.into());
+        if let Some(inst_id) = inst_id {
+            params.insert("instId".into(), inst_id.into());
+        }
+        if let Some(uly) = uly {
+            params.insert("uly".into(), uly.into());
+        }
+        if let Some(inst_family) = inst_family {
+            params.insert("instFamily".into(), inst_family.into());
+        }
+
+        self
+            .get::<RestApi<TradeFee>>("/api/v5/account/trade-fee", &params)
+            .await?.to_result_one()
+    }
+
+    /// 获取杠杆倍数，inst_id可以传多个，用逗号分隔，最多20个
+    /// 限速：20次/2s
+    /// 限速规则：UserID
+    /// GET /api/v5/account/leverage-info
+    pub async fn account_leverage_info(
+        &self,
+        inst_id: impl Into<String>,
+        mgn_mode: TradeMode,
+    ) -> Result<Vec<LeverageInfo>> {
+        self.limit_mgr().check_limit(APiEnum::AccountLeverageInfo, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        params.insert("instId".into(), inst_id.into());
+        params.insert("mgnMode".into(), mgn_mode.into());
+
+        self
+            .get::<RestApi<LeverageInfo>>("/api/v5/account/leverage-info", &params)
+            .await?.to_result()
+    }
+
+    /// 设置持仓方式，仅适用交割/永续
+    /// 切换前需要先平掉所有仓位并撤销所有挂单
+    /// 限速：5次/2s
+    /// 限速规则：UserID
+    /// POST /api/v5/account/set-position-mode
+    pub async fn account_set_position_mode(&self, pos_mode: PositionMode) -> Result<SetPositionMode> {
+        self.limit_mgr().check_limit(APiEnum::AccountSetPositionMode, 1).await?;
+
+        self
+            .post::<RestApi<SetPositionMode>>("/api/v5/account/set-position-mode", &SetPositionMode { pos_mode })
+            .await?.to_result_one()
+    }
+
+    /// 设置希腊字母的展示方式
+    /// 限速：5次/2s
+    /// 限速规则：UserID
+    /// POST /api/v5/account/set-greeks
+    pub async fn account_set_greeks(&self, greeks_type: GreeksType) -> Result<SetGreeks> {
+        self.limit_mgr().check_limit(APiEnum::AccountSetGreeks, 1).await?;
+
+        self
+            .post::<RestApi<SetGreeks>>("/api/v5/account/set-greeks", &SetGreeks { greeks_type })
+            .await?.to_result_one()
+    }
+
+    /// 逐仓交易设置，设置逐仓保证金的划转模式
+    /// 币币杠杆支持automatic、quick_margin，合约支持automatic
+    /// 限速：5次/2s
+    /// 限速规则：UserID
+    /// POST /api/v5/account/set-isolated-mode
+    pub async fn account_set_isolated_mode(&self, iso_mode: IsoMode, mode_type: IsoModeType) -> Result<SetIsolatedMode> {
+        self.limit_mgr().check_limit(APiEnum::AccountSetIsolatedMode, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        params.insert("isoMode".into(), iso_mode.into());
+        params.insert("type".into(), mode_type.into());
+
+        self
+            .post::<RestApi<SetIsolatedMode>>("/api/v5/account/set-isolated-mode", &params)
+            .await?.to_result_one()
+    }
+
+    /// 增加或者减少逐仓保证金，减少保证金可能会导致实际杠杆倍数发生变化
+    /// ccy仅适用于逐仓杠杆仓位
+    /// 限速：20次/2s
+    /// 限速规则：UserID
+    /// POST /api/v5/account/position/margin-balance
+    pub async fn account_margin_balance(
+        &self,
+        inst_id: impl Into<String>,
+        pos_side: PositionSide,
+        balance_type: MarginBalanceType,
+        amt: Decimal,
+        ccy: Option<impl Into<String>>,
+    ) -> Result<MarginBalance> {
+        self.limit_mgr().check_limit(APiEnum::AccountMarginBalance, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        params.insert("instId".into(), inst_id.into());
+        params.insert("posSide".into(), pos_side.into());
+        params.insert("type".into(), balance_type.into());
+        params.insert("amt".into(), amt.to_string());
+        if let Some(ccy) = ccy {
+            params.insert("ccy".into(), ccy.into());
+        }
+
+        self
+            .post::<RestApi<MarginBalance>>("/api/v5/account/position/margin-balance", &params)
+            .await?.to_result_one()
+    }
+
+    /// 设置自动借币，仅适用于跨币种保证金模式和组合保证金模式
+    /// 限速：5次/2s
+    /// 限速规则：UserID
+    /// POST /api/v5/account/set-auto-loan
+    pub async fn account_set_auto_loan(&self, auto_loan: bool) -> Result<SetAutoLoan> {
+        self.limit_mgr().check_limit(APiEnum::AccountSetAutoLoan, 1).await?;
+
+        self
+            .post::<RestApi<SetAutoLoan>>("/api/v5/account/set-auto-loan", &SetAutoLoan { auto_loan })
+            .await?.to_result_one()
+    }
+
+    async fn get_bills(&self, path: &str, filter: &BillsFilter) -> Result<Vec<Bill>> {
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(inst_type) = filter.inst_type {
+            params.insert("instType".into(), inst_type.into());
+        }
+        if let Some(inst_id) = &filter.inst_id {
+            params.insert("instId".into(), inst_id.into());
+        }
+        if let Some(ccy) = &filter.ccy {
+            params.insert("ccy".into(), ccy.into());
+        }
+        if let Some(mgn_mode) = filter.mgn_mode {
+            params.insert("mgnMode".into(), mgn_mode.into());
+        }
+        if let Some(ct_type) = &filter.ct_type {
+            params.insert("ctType".into(), ct_type.into());
+        }
+        if let Some(bill_type) = filter.bill_type {
+            params.insert("type".into(), bill_type.to_string());
+        }
+        if let Some(sub_type) = filter.sub_type {
+            params.insert("subType".into(), sub_type.to_string());
+        }
+        if let Some(after) = &filter.after {
+            params.insert("after".into(), after.into());
+        }
+        if let Some(before) = &filter.before {
+            params.insert("before".into(), before.into());
+        }
+        if let Some(begin) = filter.begin {
+            params.insert("begin".into(), begin.to_string());
+        }
+        if let Some(end) = filter.end {
+            params.insert("end".into(), end.to_string());
+        }
+        if let Some(limit) = filter.limit {
+            params.insert("limit".into(), limit.to_string());
+        }
+
+        self
+            .get::<RestApi<Bill>>(path, &params)
+            .await?.to_result()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use futures_util::StreamExt;
+    use rust_decimal::Decimal;
+    use crate::{Bill, BillSubType, BillType, BillsFilter, ExecuteType, InstType, MarginBalanceType, MockTransport, OkxAccountClient, PositionMode, PositionSide, TradeMode, TradeSide};
+
+    #[tokio::test]
+    pub async fn test_bills_stream() {
+        let bill = |bill_id: &str, sub_type: &str| format!(r#"{{"bal":"8694.2179403378290202","balChg":"0.0219338232210000","billId":"{bill_id}",
+            "ccy":"USDT","clOrdId":"","execType":"T","fee":"-0.000021955779","fillFwdPx":"","fillIdxPx":"27104.1","fillMarkPx":"","fillMarkVol":"",
+            "fillPxUsd":"","fillPxVol":"","fillTime":"1695033476166","from":"","instId":"BTC-USDT","instType":"SPOT","interest":"0","mgnMode":"isolated",
+            "notes":"","ordId":"623950854533513219","pnl":"0","posBal":"0","posBalChg":"0","px":"27105.9","subType":"{sub_type}","sz":"0.021955779",
+            "tag":"","to":"","tradeId":"586760148","ts":"1695033476167","type":"2"}}"#);
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(format!(r#"{{"code":"0","msg":"","data":[{},{}]}}"#, bill("3", "1"), bill("2", "2")));
+        transport.push_json(format!(r#"{{"code":"0","msg":"","data":[{}]}}"#, bill("1", "999")));
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport.clone());
+
+        let filter = BillsFilter {
+            ccy: Some("USDT".into()),
+            bill_type: Some(BillType::Trade),
+            limit: Some(2),
+            ..Default::default()
+        };
+        let bills: Vec<Bill> = client.account_bills_stream(filter, true).map(|item| item.unwrap()).collect().await;
+        assert_eq!(bills.len(), 3);
+        assert_eq!(bills[0].bill_type, BillType::Trade);
+        assert_eq!(bills[0].sub_type, BillSubType::Buy);
+        assert_eq!(bills[0].inst_type, Some(InstType::Spot));
+        assert_eq!(bills[0].mgn_mode, Some(TradeMode::Isolated));
+        assert_eq!(bills[2].sub_type, BillSubType::Other(999));
+        assert_eq!(bills[0].fee, Some(Decimal::new(-21955779, 12)));
+
+        let requests = transport.requests();
+        assert_eq!(requests[0].url, "https://www.okx.com/api/v5/account/bills-archive?ccy=USDT&limit=2&type=2");
+        assert_eq!(requests[1].url, "https://www.okx.com/api/v5/account/bills-archive?after=2&ccy=USDT&limit=2&type=2");
+
+        // 资金划转等不涉及产品的账单instType、mgnMode为空
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"bal":"8694.2","balChg":"100","billId":"4","ccy":"USDT","clOrdId":"","execType":"",
+            "fee":"0","fillTime":"","from":"6","instId":"","instType":"","interest":"0","mgnMode":"","notes":"","ordId":"","pnl":"0","posBal":"",
+            "posBalChg":"","px":"","subType":"11","sz":"100","tag":"","to":"18","tradeId":"","ts":"1695033476167","type":"1"}]}"#);
+        let bills = client.account_bills(BillsFilter::default()).await.unwrap();
+        assert_eq!(bills[0].bill_type, BillType::Transfer);
+        assert_eq!(bills[0].inst_type, None);
+        assert_eq!(bills[0].mgn_mode, None);
+        assert_eq!(bills[0].exec_type, ExecuteType::None);
+
+        // limit超过100时按每页100条判断是否还有下一页
+        let page: Vec<String> = (0..100).map(|id| bill(&(1000 - id).to_string(), "1")).collect();
+        transport.push_json(format!(r#"{{"code":"0","msg":"","data":[{}]}}"#, page.join(",")));
+        transport.push_json(format!(r#"{{"code":"0","msg":"","data":[{}]}}"#, bill("800", "1")));
+        let filter = BillsFilter { limit: Some(500), ..Default::default() };
+        let bills: Vec<Bill> = client.account_bills_stream(filter, false).map(|item| item.unwrap()).collect().await;
+        assert_eq!(bills.len(), 101);
+        assert_eq!(transport.requests()[4].url, "https://www.okx.com/api/v5/account/bills?after=901&limit=500");
+    }
+
+    #[tokio::test]
+    pub async fn test_max_size_and_trade_fee() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"ccy":"","instId":"BTC-USDT-SWAP","maxBuy":"1201","maxSell":"1201"}]}"#);
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"category":"1","delivery":"","exercise":"","fiat":[],"instType":"SWAP","level":"Lv1",
+            "maker":"-0.0002","makerU":"-0.0002","makerUSDC":"","taker":"-0.0005","takerU":"-0.0005","takerUSDC":"","ts":"1707802474069"}]}"#);
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport.clone());
+
+        let result = client.account_max_size("BTC-USDT-SWAP", TradeMode::Cross, None::<String>, Some(Decimal::from(30000)), None).await.unwrap();
+        assert_eq!(result[0].max(TradeSide::Buy), Decimal::from(1201));
+        assert_eq!(transport.requests()[0].url, "https://www.okx.com/api/v5/account/max-size?instId=BTC-USDT-SWAP&px=30000&tdMode=cross");
+
+        let fee = client.account_trade_fee(InstType::Swap, None::<String>, None::<String>, Some("BTC-USDT")).await.unwrap();
+        assert_eq!(fee.level, "Lv1");
+        assert_eq!(fee.taker_usdc, None);
+        assert_eq!(fee.expected_fee(Decimal::from(10000), ExecuteType::Taker, "USDT"), Decimal::from(-5));
+        assert_eq!(fee.fee_rate(ExecuteType::Maker, "USDC"), Decimal::new(-2, 4));
+    }
+
+    #[tokio::test]
+    pub async fn test_account_mode() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"posMode":"long_short_mode"}]}"#);
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"amt":"0.3","ccy":"BTC","instId":"BTC-USDT","leverage":"","posSide":"net","type":"add"}]}"#);
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"autoLoan":true}]}"#);
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport.clone());
+
+        let result = client.account_set_position_mode(PositionMode::LongShortMode).await.unwrap();
+        assert_eq!(result.pos_mode, PositionMode::LongShortMode);
+        assert_eq!(transport.requests()[0].body.as_deref(), Some(r#"{"posMode":"long_short_mode"}"#));
+
+        let result = client.account_margin_balance("BTC-USDT", PositionSide::Net, MarginBalanceType::Add, Decimal::new(3, 1), Some("BTC")).await.unwrap();
+        assert_eq!(result.balance_type, MarginBalanceType::Add);
+        assert_eq!(result.amt, Decimal::new(3, 1));
+        assert_eq!(result.leverage, None);
+        assert_eq!(transport.requests()[1].body.as_deref(), Some(r#"{"amt":"0.3","ccy":"BTC","instId":"BTC-USDT","posSide":"net","type":"add"}"#));
+
+        assert!(client.account_set_auto_loan(true).await.unwrap().auto_loan);
+        assert_eq!(transport.requests()[2].body.as_deref(), Some(r#"{"autoLoan":true}"#));
+    }
 }
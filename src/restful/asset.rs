@@ -0,0 +1,437 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::api_enum::APiEnum;
+use crate::apikey::OkxAccountClient;
+use crate::okx_error::*;
+use super::models::{ApiKeyPermission, AssetBalance, AssetBill, AssetBillType, AssetCurrency, AssetTransfer, AssetTransferState, AssetValuation, CancelWithdrawal, DepositAddress, DepositHistory, DepositState, RestApi, TransferRequest, TransferType, Withdrawal, WithdrawalHistory, WithdrawalRequest, WithdrawalState};
+
+/// 资金流水的查询条件
+/// after、before为Unix时间戳的毫秒数
+#[derive(Serialize, Debug, Deserialize, Default, Clone)]
+pub struct AssetBillsFilter {
+    pub ccy: Option<String>,
+    #[serde(rename="type")]
+    pub bill_type: Option<AssetBillType>,
+    /// 转账或提币的客户自定义ID
+    #[serde(rename="clientId")]
+    pub client_id: Option<String>,
+    /// 查询在此之前的内容
+    pub after: Option<i64>,
+    /// 查询在此之后的内容
+    pub before: Option<i64>,
+    /// 返回结果的数量，最大为100，默认100条
+    pub limit: Option<usize>,
+}
+
+/// 充值记录的查询条件
+/// after、before为Unix时间戳的毫秒数
+#[derive(Serialize, Debug, Deserialize, Default, Clone)]
+pub struct DepositHistoryFilter {
+    pub ccy: Option<String>,
+    /// 充值记录ID
+    #[serde(rename="depId")]
+    pub dep_id: Option<String>,
+    /// 区块转账哈希记录
+    #[serde(rename="txId")]
+    pub tx_id: Option<String>,
+    pub state: Option<DepositState>,
+    /// 查询在此之前的内容
+    pub after: Option<i64>,
+    /// 查询在此之后的内容
+    pub before: Option<i64>,
+    /// 返回结果的数量，最大为100，默认100条
+    pub limit: Option<usize>,
+}
+
+/// 提币记录的查询条件
+/// after、before为Unix时间戳的毫秒数
+#[derive(Serialize, Debug, Deserialize, Default, Clone)]
+pub struct WithdrawalHistoryFilter {
+    pub ccy: Option<String>,
+    /// 提币申请ID
+    #[serde(rename="wdId")]
+    pub wd_id: Option<String>,
+    #[serde(rename="clientId")]
+    pub client_id: Option<String>,
+    #[serde(rename="txId")]
+    pub tx_id: Option<String>,
+    pub state: Option<WithdrawalState>,
+    /// 查询在此之前的内容
+    pub after: Option<i64>,
+    /// 查询在此之后的内容
+    pub before: Option<i64>,
+    /// 返回结果的数量，最大为100，默认100条
+    pub limit: Option<usize>,
+}
+
+impl OkxAccountClient {
+    /// 获取资金账户所有资产列表，查询各币种的余额、冻结和可用等信息
+    /// 限速：6次/s
+    /// 限速规则：UserID
+    /// GET /api/v5/asset/balances
+    pub async fn asset_balances(&self, ccy_list: Option<Vec<String>>) -> Result<Vec<AssetBalance>> {
+        self.limit_mgr().check_limit(APiEnum::AssetBalances, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(val) = ccy_list {
+            params.insert("ccy".into(), val.join(","));
+        }
+
+        self
+            .get::<RestApi<AssetBalance>>("/api/v5/asset/balances", &params)
+            .await?.to_result()
+    }
+
+    /// 获取当前用户KYC实体下可以进行充提的币种列表
+    /// 限速：6次/s
+    /// 限速规则：UserID
+    /// GET /api/v5/asset/currencies
+    pub async fn asset_currencies(&self, ccy_list: Option<Vec<String>>) -> Result<Vec<AssetCurrency>> {
+        self.limit_mgr().check_limit(APiEnum::AssetCurrencies, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(val) = ccy_list {
+            params.insert("ccy".into(), val.join(","));
+        }
+
+        self
+            .get::<RestApi<AssetCurrency>>("/api/v5/asset/currencies", &params)
+            .await?.to_result()
+    }
+
+    /// 资金划转，支持母账户的资金账户划转到交易账户，母账户到子账户的资金账户和交易账户划转
+    /// 限速：2次/s
+    /// 限速规则：UserID + Currency
+    /// POST /api/v5/asset/transfer
+    pub async fn asset_transfer(&self, request: TransferRequest) -> Result<AssetTransfer> {
+        self.limit_mgr().check_limit_with_ccy(APiEnum::AssetTransfer, &request.ccy, 1).await?;
+
+        self
+            .post::<RestApi<AssetTransfer>>("/api/v5/asset/transfer", &request)
+            .await?.to_result_one()
+    }
+
+    /// 获取资金划转状态，trans_id和client_id必须传一个，若传两个，以trans_id为主
+    /// 查询母子账户间的划转时transfer_type必填，默认为账户内划转
+    /// 限速：10次/s
+    /// 限速规则：UserID
+    /// GET /api/v5/asset/transfer-state
+    pub async fn asset_transfer_state(
+        &self,
+        trans_id: Option<impl Into<String>>,
+        client_id: Option<impl Into<String>>,
+        transfer_type: Option<TransferType>,
+    ) -> Result<Option<AssetTransferState>> {
+        self.limit_mgr().check_limit(APiEnum::AssetTransferState, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(trans_id) = trans_id {
+            params.insert("transId".into(), trans_id.into());
+        }
+        if let Some(client_id) = client_id {
+            params.insert("clientId".into(), client_id.into());
+        }
+        if let Some(transfer_type) = transfer_type {
+            params.insert("type".into(), transfer_type.into());
+        }
+
+        self
+            .get::<RestApi<AssetTransferState>>("/api/v5/asset/transfer-state", &params)
+            .await?.to_result_one_opt()
+    }
+
+    /// 查询资金账户账单流水，可以查询最近一个月的数据
+    /// 限速：6次/s
+    /// 限速规则：UserID
+    /// GET /api/v5/asset/bills
+    pub async fn asset_bills(&self, filter: AssetBillsFilter) -> Result<Vec<AssetBill>> {
+        self.limit_mgr().check_limit(APiEnum::AssetBills, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(ccy) = filter.ccy {
+            params.insert("ccy".into(), ccy);
+        }
+        if let Some(bill_type) = filter.bill_type {
+            params.insert("type".into(), bill_type.to_string());
+        }
+        if let Some(client_id) = filter.client_id {
+            params.insert("clientId".into(), client_id);
+        }
+        if let Some(after) = filter.after {
+            params.insert("after".into(), after.to_string());
+        }
+        if let Some(before) = filter.before {
+            params.insert("before".into(), before.to_string());
+        }
+        if let Some(limit) = filter.limit {
+            params.insert("limit".into(), limit.to_string());
+        }
+
+        self
+            .get::<RestApi<AssetBill>>("/api/v5/asset/bills", &params)
+            .await?.to_result()
+    }
+
+    /// 获取账户资产估值，ccy为估值的单位，默认为BTC
+    /// 限速：1次/2s
+    /// 限速规则：UserID
+    /// GET /api/v5/asset/asset-valuation
+    pub async fn asset_valuation(&self, ccy: Option<impl Into<String>>) -> Result<AssetValuation> {
+        self.limit_mgr().check_limit(APiEnum::AssetValuation, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(ccy) = ccy {
+            params.insert("ccy".into(), ccy.into());
+        }
+
+        self
+            .get::<RestApi<AssetValuation>>("/api/v5/asset/asset-valuation", &params)
+            .await?.to_result_one()
+    }
+}
+
+impl OkxAccountClient {
+    /// 获取各个币种的充值地址，包括曾使用过的老地址
+    /// 限速：6次/s
+    /// 限速规则：UserID
+    /// GET /api/v5/asset/deposit-address
+    pub async fn deposit_address(&self, ccy: impl Into<String>) -> Result<Vec<DepositAddress>> {
+        self.limit_mgr().check_limit(APiEnum::AssetDepositAddress, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+        params.insert("ccy".into(), ccy.into());
+
+        self
+            .get::<RestApi<DepositAddress>>("/api/v5/asset/deposit-address", &params)
+            .await?.to_result()
+    }
+
+    /// 根据币种、充值状态、时间范围获取充值记录，按照时间倒序排列
+    /// 限速：6次/s
+    /// 限速规则：UserID
+    /// GET /api/v5/asset/deposit-history
+    pub async fn deposit_history(&self, filter: DepositHistoryFilter) -> Result<Vec<DepositHistory>> {
+        self.limit_mgr().check_limit(APiEnum::AssetDepositHistory, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(ccy) = filter.ccy {
+            params.insert("ccy".into(), ccy);
+        }
+        if let Some(dep_id) = filter.dep_id {
+            params.insert("depId".into(), dep_id);
+        }
+        if let Some(tx_id) = filter.tx_id {
+            params.insert("txId".into(), tx_id);
+        }
+        if let Some(state) = filter.state {
+            params.insert("state".into(), state.to_string());
+        }
+        if let Some(after) = filter.after {
+            params.insert("after".into(), after.to_string());
+        }
+        if let Some(before) = filter.before {
+            params.insert("before".into(), before.to_string());
+        }
+        if let Some(limit) = filter.limit {
+            params.insert("limit".into(), limit.to_string());
+        }
+
+        self
+            .get::<RestApi<DepositHistory>>("/api/v5/asset/deposit-history", &params)
+            .await?.to_result()
+    }
+
+    /// 提币，需要客户端通过with_withdrawal_enabled开启，且API key有withdraw权限
+    /// 限速：6次/s
+    /// 限速规则：UserID
+    /// POST /api/v5/asset/withdrawal
+    pub async fn withdrawal(&self, request: WithdrawalRequest) -> Result<Withdrawal> {
+        self.check_withdrawal_permission().await?;
+        self.limit_mgr().check_limit(APiEnum::AssetWithdrawal, 1).await?;
+
+        self
+            .post::<RestApi<Withdrawal>>("/api/v5/asset/withdrawal", &request)
+            .await?.to_result_one()
+    }
+
+    /// 撤销普通提币，不支持撤销闪电网络提币，需要客户端通过with_withdrawal_enabled开启
+    /// 限速：6次/s
+    /// 限速规则：UserID
+    /// POST /api/v5/asset/cancel-withdrawal
+    pub async fn cancel_withdrawal(&self, wd_id: impl Into<String>) -> Result<CancelWithdrawal> {
+        self.check_withdrawal_permission().await?;
+        self.limit_mgr().check_limit(APiEnum::AssetCancelWithdrawal, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+        params.insert("wdId".into(), wd_id.into());
+
+        self
+            .post::<RestApi<CancelWithdrawal>>("/api/v5/asset/cancel-withdrawal", &params)
+            .await?.to_result_one()
+    }
+
+    /// 根据币种、提币状态、时间范围获取提币记录，按照时间倒序排列
+    /// 限速：6次/s
+    /// 限速规则：UserID
+    /// GET /api/v5/asset/withdrawal-history
+    pub async fn withdrawal_history(&self, filter: WithdrawalHistoryFilter) -> Result<Vec<WithdrawalHistory>> {
+        self.limit_mgr().check_limit(APiEnum::AssetWithdrawalHistory, 1).await?;
+        self.get_withdrawal_history(&filter).await
+    }
+
+    /// 每隔interval查询一次提币记录，直到提币成功、失败或撤销
+    /// 超过timeout时返回最后一次查询到的记录，需要通过state.is_terminal()判断是否完成
+    pub async fn wait_withdrawal(&self, wd_id: &str, interval: Duration, timeout: Duration) -> Result<WithdrawalHistory> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        let filter = WithdrawalHistoryFilter {
+            wd_id: Some(wd_id.to_string()),
+            ..Default::default()
+        };
+
+        loop {
+            self.limit_mgr().wait_limit(APiEnum::AssetWithdrawalHistory, 1).await?;
+            let last = self.get_withdrawal_history(&filter).await?
+                .into_iter()
+                .find(|item| item.wd_id == wd_id);
+
+            match last {
+                Some(item) if item.state.is_terminal() => return Ok(item),
+                Some(item) if tokio::time::Instant::now() + interval > deadline => return Ok(item),
+                None if tokio::time::Instant::now() + interval > deadline => return Err(OkxError::EmptyData),
+                _ => tokio::time::sleep(interval).await,
+            }
+        }
+    }
+
+    async fn get_withdrawal_history(&self, filter: &WithdrawalHistoryFilter) -> Result<Vec<WithdrawalHistory>> {
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(ccy) = &filter.ccy {
+            params.insert("ccy".into(), ccy.into());
+        }
+        if let Some(wd_id) = &filter.wd_id {
+            params.insert("wdId".into(), wd_id.into());
+        }
+        if let Some(client_id) = &filter.client_id {
+            params.insert("clientId".into(), client_id.into());
+        }
+        if let Some(tx_id) = &filter.tx_id {
+            params.insert("txId".into(), tx_id.into());
+        }
+        if let Some(state) = &filter.state {
+            params.insert("state".into(), state.to_string());
+        }
+        if let Some(after) = &filter.after {
+            params.insert("after".into(), after.to_string());
+        }
+        if let Some(before) = &filter.before {
+            params.insert("before".into(), before.to_string());
+        }
+        if let Some(limit) = &filter.limit {
+            params.insert("limit".into(), limit.to_string());
+        }
+
+        self
+            .get::<RestApi<WithdrawalHistory>>("/api/v5/asset/withdrawal-history", &params)
+            .await?.to_result()
+    }
+
+    async fn check_withdrawal_permission(&self) -> Result<()> {
+        if !self.withdrawal_enabled() {
+            return Err(OkxError::WithdrawalNotPermitted("withdrawal is disabled on this client".to_string()));
+        }
+
+        // API key的权限不会变化，只在第一次提币时查询，查询失败时下次重新查询
+        let permitted = self.withdrawal_perm()
+            .get_or_try_init(|| async {
+                self.account_config().await.map(|config| config.permission().contains(&ApiKeyPermission::Withdraw))
+            })
+            .await?;
+        if !permitted {
+            return Err(OkxError::WithdrawalNotPermitted("api key has no withdraw permission".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use rust_decimal::Decimal;
+    use crate::{AccountType, MockTransport, OkxAccountClient, OkxError, TransferRequest, TransferState, TransferType, WithdrawalRequest, WithdrawalState};
+
+    #[tokio::test]
+    pub async fn test_transfer() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"transId":"754147","ccy":"USDT","clientId":"t1","from":"6","amt":"1.5","to":"18"}]}"#);
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"amt":"1.5","ccy":"USDT","clientId":"t1","from":"6","instId":"","state":"success",
+            "subAcct":"","to":"18","toInstId":"","transId":"754147","type":"0"}]}"#);
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport.clone());
+
+        let result = client.asset_transfer(TransferRequest::funding_to_trading("USDT", Decimal::new(15, 1)).with_client_id("t1")).await.unwrap();
+        assert_eq!(result.trans_id, "754147");
+        assert_eq!(result.to, AccountType::Trading);
+        assert_eq!(transport.requests()[0].body.as_deref(),
+            Some(r#"{"ccy":"USDT","amt":"1.5","from":"6","to":"18","type":"0","clientId":"t1"}"#));
+
+        let state = client.asset_transfer_state(Some("754147"), None::<String>, Some(TransferType::WithinAccount)).await.unwrap().unwrap();
+        assert_eq!(state.state, TransferState::Success);
+        assert_eq!(transport.requests()[1].url, "https://www.okx.com/api/v5/asset/transfer-state?transId=754147&type=0");
+
+        // 同一币种限速2次/s
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"transId":"754148","ccy":"USDT","clientId":"","from":"18","amt":"1","to":"6"}]}"#);
+        let result = client.asset_transfer(TransferRequest::trading_to_funding("USDT", Decimal::ONE)).await.unwrap();
+        assert_eq!(result.trans_id, "754148");
+        let result = client.asset_transfer(TransferRequest::trading_to_funding("USDT", Decimal::ONE)).await;
+        assert!(matches!(result, Err(OkxError::RateLimit)));
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    fn account_config_resp(perm: &str) -> String {
+        format!(r#"{{"code":"0","msg":"","data":[{{"acctLv":"2","autoLoan":false,"ctIsoMode":"automatic","greeksType":"PA","level":"Lv1",
+            "levelTmp":"","mgnIsoMode":"automatic","posMode":"long_short_mode","spotOffsetType":"","uid":"44705892343619584",
+            "mainUid":"44705892343619584","roleType":"0","traderInsts":[],"opAuth":"0","kycLv":"3","label":"v5","ip":"","perm":"{}"}}]}}"#, perm)
+    }
+
+    #[tokio::test]
+    pub async fn test_withdrawal() {
+        let transport = Arc::new(MockTransport::new());
+        let request = WithdrawalRequest::on_chain("USDT", Decimal::from(10), "0x1234", "USDT-ERC20");
+
+        // 未开启提币时不会发出任何请求
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport.clone());
+        let result = client.withdrawal(request.clone()).await;
+        assert!(matches!(result, Err(OkxError::WithdrawalNotPermitted(_))));
+        assert!(transport.requests().is_empty());
+
+        // API key没有withdraw权限，权限只查询一次
+        let client = client.with_withdrawal_enabled();
+        transport.push_json(account_config_resp("read_only,trade"));
+        for _ in 0..2 {
+            let result = client.withdrawal(request.clone()).await;
+            assert!(matches!(result, Err(OkxError::WithdrawalNotPermitted(_))));
+        }
+        assert_eq!(transport.requests().len(), 1);
+
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport.clone())
+            .with_withdrawal_enabled();
+        transport.push_json(account_config_resp("read_only,withdraw,trade"));
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"amt":"10","wdId":"67485","ccy":"USDT","clientId":"","chain":"USDT-ERC20"}]}"#);
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"wdId":"67485"}]}"#);
+        let result = client.withdrawal(request).await.unwrap();
+        assert_eq!(result.wd_id, "67485");
+        assert_eq!(transport.requests()[2].body.as_deref(),
+            Some(r#"{"ccy":"USDT","amt":"10","dest":"4","toAddr":"0x1234","chain":"USDT-ERC20"}"#));
+        assert_eq!(client.cancel_withdrawal("67485").await.unwrap().wd_id, "67485");
+        assert_eq!(transport.requests()[3].url, "https://www.okx.com/api/v5/asset/cancel-withdrawal");
+
+        let history = |state: i32| format!(r#"{{"code":"0","msg":"","data":[{{"chain":"USDT-ERC20","fee":"1.5","feeCcy":"USDT","ccy":"USDT","clientId":"",
+            "amt":"10","txId":"","from":"","to":"0x1234","state":"{}","ts":"1655251200000","wdId":"67485"}}]}}"#, state);
+        transport.push_json(history(1));
+        transport.push_json(history(2));
+        let result = client.wait_withdrawal("67485", Duration::from_millis(1), Duration::from_secs(5)).await.unwrap();
+        assert_eq!(result.state, WithdrawalState::Success);
+        assert_eq!(transport.requests()[4].url, "https://www.okx.com/api/v5/asset/withdrawal-history?wdId=67485");
+        assert_eq!(transport.requests().len(), 6);
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::BTreeMap;
 use crate::api_enum::APiEnum;
 use crate::okx_error::*;
-use super::models::{MarketBooks, MarketTicker, MarketTickers, RestApi, Trade};
+use super::models::{Bar, Candle, CandleType, MarketBooks, MarketTicker, MarketTickers, RestApi, Trade};
 use crate::apikey::OkxPublicClient;
 
 impl OkxPublicClient {
@@ -15,7 +15,7 @@ impl OkxPublicClient {
         inst_family: Option<impl Into<String>>,
     ) -> Result<Vec<MarketTickers>>
     {
-        self.limit_mgr().check_limit(APiEnum::MarketTickers as u32, 1, 20, 2)?;
+        self.limit_mgr().check_limit(APiEnum::MarketTickers, 1).await?;
         let mut params: BTreeMap<String, String> = BTreeMap::new();
 
         if let Some(uly) = uly {
@@ -39,7 +39,7 @@ impl OkxPublicClient {
     pub async fn market_ticker(&self, inst_id: impl Into<String>) -> Result<MarketTicker>
     {
         //  /api/v5/market/ticker
-        self.limit_mgr().check_limit(APiEnum::MarketTicker as u32, 1, 20, 2)?;
+        self.limit_mgr().check_limit(APiEnum::MarketTicker, 1).await?;
         let mut params: BTreeMap<String, String> = BTreeMap::new();
 
         params.insert("instId".into(), inst_id.into());
@@ -52,7 +52,7 @@ impl OkxPublicClient {
     /// 获取交易产品公共成交数据
     /// GET /api/v5/market/trades
     pub async fn market_trades(&self, inst_id: impl Into<String>, limit: usize) -> Result<Vec<Trade>>{
-        self.limit_mgr().check_limit(APiEnum::MarketTrades as u32, 1, 100, 2)?;
+        self.limit_mgr().check_limit(APiEnum::MarketTrades, 1).await?;
         let mut params: BTreeMap<String, String> = BTreeMap::new();
 
         // /api/v5/market/trades
@@ -69,7 +69,7 @@ impl OkxPublicClient {
     pub async fn market_books(&self, inst_id: impl Into<String>, sz: Option<impl Into<String>>) -> Result<Vec<MarketBooks>>
     {
         // /api/v5/market/books
-        self.limit_mgr().check_limit(APiEnum::MarketBooks as u32, 1, 40, 2)?;
+        self.limit_mgr().check_limit(APiEnum::MarketBooks, 1).await?;
         let mut params: BTreeMap<String, String> = BTreeMap::new();
 
         params.insert("instId".into(), inst_id.into());
@@ -90,4 +90,135 @@ impl OkxPublicClient {
             .get::<RestApi<MarketBooks>>("/api/v5/market/books", &params)
             .await?.to_result()
     }
+
+    /// 获取K线数据，最多可获取最近1440条
+    /// 限速：40次/2s
+    /// GET /api/v5/market/candles
+    pub async fn market_candles(&self, inst_id: impl Into<String>, bar: Option<Bar>, after: Option<i64>, before: Option<i64>, limit: Option<usize>) -> Result<Vec<Candle>> {
+        self.limit_mgr().check_limit(APiEnum::MarketCandles, 1).await?;
+        self.get_candles(CandleType::Candles, inst_id.into(), bar, after, before, limit).await
+    }
+
+    /// 获取交易产品历史K线数据，最近几年的数据
+    /// 限速：20次/2s
+    /// GET /api/v5/market/history-candles
+    pub async fn market_history_candles(&self, inst_id: impl Into<String>, bar: Option<Bar>, after: Option<i64>, before: Option<i64>, limit: Option<usize>) -> Result<Vec<Candle>> {
+        self.limit_mgr().check_limit(APiEnum::MarketHistoryCandles, 1).await?;
+        self.get_candles(CandleType::HistoryCandles, inst_id.into(), bar, after, before, limit).await
+    }
+
+    /// 获取指数K线数据，inst_id为现货指数，如 BTC-USD
+    /// 限速：20次/2s
+    /// GET /api/v5/market/index-candles
+    pub async fn market_index_candles(&self, inst_id: impl Into<String>, bar: Option<Bar>, after: Option<i64>, before: Option<i64>, limit: Option<usize>) -> Result<Vec<Candle>> {
+        self.limit_mgr().check_limit(APiEnum::MarketIndexCandles, 1).await?;
+        self.get_candles(CandleType::IndexCandles, inst_id.into(), bar, after, before, limit).await
+    }
+
+    /// 获取标记价格K线数据
+    /// 限速：20次/2s
+    /// GET /api/v5/market/mark-price-candles
+    pub async fn market_mark_price_candles(&self, inst_id: impl Into<String>, bar: Option<Bar>, after: Option<i64>, before: Option<i64>, limit: Option<usize>) -> Result<Vec<Candle>> {
+        self.limit_mgr().check_limit(APiEnum::MarketMarkPriceCandles, 1).await?;
+        self.get_candles(CandleType::MarkPriceCandles, inst_id.into(), bar, after, before, limit).await
+    }
+
+    /// 下载[start, end]时间范围内的K线，按时间升序返回，单位：毫秒
+    /// 从end开始用after向前翻页，每页都会等待本地限速的额度，不会因为本地限速失败
+    pub async fn market_candles_range(&self, candle_type: CandleType, inst_id: impl Into<String>, bar: Bar, start: i64, end: i64) -> Result<Vec<Candle>> {
+        let inst_id = inst_id.into();
+        let (api, page_size) = match candle_type {
+            CandleType::Candles => (APiEnum::MarketCandles, 300),
+            CandleType::HistoryCandles => (APiEnum::MarketHistoryCandles, 100),
+            CandleType::IndexCandles => (APiEnum::MarketIndexCandles, 100),
+            CandleType::MarkPriceCandles => (APiEnum::MarketMarkPriceCandles, 100),
+        };
+
+        let mut result = vec![];
+        // after: 请求此时间戳之前的数据，不包含after本身
+        let mut after = end + 1;
+        while after > start {
+            self.limit_mgr().wait_limit(api, 1).await?;
+            let page = self.get_candles(candle_type, inst_id.clone(), Some(bar), Some(after), None, Some(page_size)).await?;
+            let oldest = match page.iter().map(|item| item.ts).min() {
+                Some(oldest) if oldest < after => oldest,
+                _ => break,
+            };
+            result.extend(page.into_iter().filter(|item| item.ts >= start && item.ts <= end));
+            after = oldest;
+        }
+
+        result.sort_by_key(|item| item.ts);
+        result.dedup_by_key(|item| item.ts);
+        Ok(result)
+    }
+
+    async fn get_candles(&self, candle_type: CandleType, inst_id: String, bar: Option<Bar>, after: Option<i64>, before: Option<i64>, limit: Option<usize>) -> Result<Vec<Candle>> {
+        let path = match candle_type {
+            CandleType::Candles => "/api/v5/market/candles",
+            CandleType::HistoryCandles => "/api/v5/market/history-candles",
+            CandleType::IndexCandles => "/api/v5/market/index-candles",
+            CandleType::MarkPriceCandles => "/api/v5/market/mark-price-candles",
+        };
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        params.insert("instId".into(), inst_id);
+        if let Some(bar) = bar {
+            params.insert("bar".into(), bar.into());
+        }
+        if let Some(after) = after {
+            params.insert("after".into(), after.to_string());
+        }
+        if let Some(before) = before {
+            params.insert("before".into(), before.to_string());
+        }
+        if let Some(limit) = limit {
+            params.insert("limit".into(), limit.to_string());
+        }
+
+        self
+            .get::<RestApi<Candle>>(path, &params)
+            .await?.to_result()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use rust_decimal::Decimal;
+    use crate::{Bar, CandleType, MockTransport, OkxPublicClient};
+
+    #[tokio::test]
+    pub async fn test_candles_range() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(r#"{"code":"0","msg":"","data":[
+            ["1597026600000","3.721","3.743","3.677","3.708","8422410","22698348.04828491","12698348.04828491","0"],
+            ["1597026540000","3.731","3.799","3.494","3.72","24912403","67632347.24399722","37632347.24399722","1"]]}"#);
+        transport.push_json(r#"{"code":"0","msg":"","data":[
+            ["1597026480000","3.73","3.74","3.71","3.72","100","372","372","1"],
+            ["1597026420000","3.70","3.75","3.69","3.73","100","372","372","1"]]}"#);
+        let pub_client = OkxPublicClient::with_transport(crate::testnet_config(), transport.clone());
+
+        let result = pub_client.market_candles_range(CandleType::Candles, "BTC-USDT", Bar::Min1, 1597026480000, 1597026600000).await.unwrap();
+        assert_eq!(result.iter().map(|item| item.ts).collect::<Vec<_>>(), vec![1597026480000, 1597026540000, 1597026600000]);
+        assert_eq!(result[0].open, Decimal::new(373, 2));
+        assert!(!result[2].confirm);
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].url, "https://www.okx.com/api/v5/market/candles?after=1597026600001&bar=1m&instId=BTC-USDT&limit=300");
+        assert_eq!(requests[1].url, "https://www.okx.com/api/v5/market/candles?after=1597026540000&bar=1m&instId=BTC-USDT&limit=300");
+    }
+
+    #[tokio::test]
+    pub async fn test_index_candles() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(r#"{"code":"0","msg":"","data":[["1597026383085","3.721","3.743","3.677","3.708","0"]]}"#);
+        let pub_client = OkxPublicClient::with_transport(crate::testnet_config(), transport);
+
+        let result = pub_client.market_index_candles("BTC-USD", None, None, None, None).await.unwrap();
+        assert_eq!(result[0].close, Decimal::new(3708, 3));
+        assert_eq!(result[0].vol, None);
+        assert_eq!(Vec::<String>::from(result[0].clone()).len(), 6);
+    }
 }
@@ -1,13 +1,20 @@
 mod account;
+mod asset;
 mod market;
 mod models;
+mod paginate;
 mod sign;
 mod trade;
 mod public;
+mod retry;
+mod transport;
 
 pub use account::*;
+pub use asset::*;
 pub use market::*;
 pub use models::*;
 pub use sign::*;
 pub use trade::*;
-pub use public::*;
\ No newline at end of file
+pub use public::*;
+pub use retry::*;
+pub use transport::*;
\ No newline at end of file
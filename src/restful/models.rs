@@ -1,8 +1,9 @@
 // use serde::de;
 use serde::{Deserialize, Serialize};
-use crate::models::de_float_from_str;
+use rust_decimal::Decimal;
 use crate::OkxError;
-use crate::utils::{from_str, to_str, to_opt_str, from_opt_str};
+use crate::okx_error::OkxErrorCode;
+use crate::utils::{from_str, to_str, to_opt_str, from_opt_str, from_opt_enum};
 
 ///////////////////
 /// // rest 通用模板
@@ -25,47 +26,217 @@ impl <T> RestApi<T> {
         if self.is_success() {
             Ok(self.data)
         } else {
-            Err(OkxError::RemoteError { code: self.code, message: self.msg})
+            Err(OkxError::RemoteError { code: OkxErrorCode::from_code(self.code), message: self.msg})
         }
     }
 
-    pub fn to_result_one(self) -> Result<T, OkxError> where T: Clone {
+    pub fn to_result_one(self) -> Result<T, OkxError> {
+        self.to_result_one_opt()?.ok_or(OkxError::EmptyData)
+    }
+
+    pub fn to_result_one_opt(self) -> Result<Option<T>, OkxError> {
         if self.is_success() {
-            Ok(self.data[0].clone())
+            Ok(self.data.into_iter().next())
         } else {
-            Err(OkxError::RemoteError { code: self.code, message: self.msg})
+            Err(OkxError::RemoteError { code: OkxErrorCode::from_code(self.code), message: self.msg})
         }
     }
+}
 
-    pub fn to_result_one_opt(self) -> Result<Option<T>, OkxError> where T: Clone {
-        if self.is_success() {
-            if self.data.is_empty() {
-                Ok(None)
-            } else {
-                Ok(Some(self.data[0].clone()))
-            }
-        } else {
-            Err(OkxError::RemoteError { code: self.code, message: self.msg})
+/// 批量操作中每一项的执行结果，由 sCode 和 sMsg 表示
+pub trait SubResult {
+    fn s_code(&self) -> &str;
+    fn s_msg(&self) -> &str;
+
+    /// sCode不为0时返回对应的错误
+    fn error(&self) -> Option<OkxError> {
+        let code = self.s_code();
+        if code.is_empty() || code == "0" {
+            return None;
+        }
+
+        Some(OkxError::RemoteError {
+            code: OkxErrorCode::from_code(code.parse().unwrap_or(-1)),
+            message: self.s_msg().to_string(),
+        })
+    }
+}
+
+impl <T: SubResult> RestApi<T> {
+    /// 批量操作的结果，顺序与请求一致。
+    /// 全部失败(code=1)或部分成功(code=2)时，okx仍会返回每一项的结果，失败的项会转换为对应的错误
+    pub fn to_sub_results(self) -> Result<Vec<Result<T, OkxError>>, OkxError> {
+        let code = OkxErrorCode::from_code(self.code);
+        let has_sub_result = (code == OkxErrorCode::OperationFailed || code == OkxErrorCode::BulkPartialSuccess) && !self.data.is_empty();
+        if !self.is_success() && !has_sub_result {
+            return Err(OkxError::RemoteError { code, message: self.msg});
         }
+
+        Ok(self.data.into_iter().map(|item| match item.error() {
+            Some(err) => Err(err),
+            None => Ok(item),
+        }).collect())
+    }
+
+    /// 单个操作的结果，sCode不为0时返回对应的错误
+    pub fn to_sub_result_one(self) -> Result<T, OkxError> {
+        self.to_sub_results()?.into_iter().next().ok_or(OkxError::EmptyData)?
     }
 }
 
+macro_rules! impl_sub_result {
+    ($($arg:tt)*) => {
+        $(
+        impl SubResult for $arg {
+            fn s_code(&self) -> &str {
+                &self.s_code
+            }
+
+            fn s_msg(&self) -> &str {
+                &self.s_msg
+            }
+        }
+        )*
+    };
+}
+
 // 查看持仓信息
 // GET /api/v5/account/positions
 
 #[derive(Clone,Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountPositions {
-    pub mgn_mode: String, //保证金模式
-
-    pub pos_side: String, //持仓方向
-
-    // 持仓数量，逐仓自主划转模式下，转入保证金后会产生pos为0的仓位
-    #[serde(deserialize_with = "de_float_from_str")]
-    pub pos: f32,
-    // 可平仓数量，适用于 币币杠杆,交割/永续（开平仓模式），期权（交易账户及保证金账户逐仓）。
-    #[serde(deserialize_with = "de_float_from_str")]
-    pub avail_pos: f32,
+    /// 产品类型
+    pub inst_type: InstType,
+    /// 产品ID，如 BTC-USDT-SWAP
+    pub inst_id: String,
+    /// 保证金模式 cross：全仓 isolated：逐仓
+    pub mgn_mode: TradeMode,
+    /// 持仓ID
+    pub pos_id: String,
+    /// 持仓方向
+    /// long：开平仓模式开多，pos为正
+    /// short：开平仓模式开空，pos为正
+    /// net：买卖模式，pos为正代表开多，pos为负代表开空
+    pub pos_side: PositionSide,
+    /// 持仓数量，逐仓自主划转模式下，转入保证金后会产生pos为0的仓位
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub pos: Decimal,
+    /// 仓位资产币种，仅适用于币币杠杆仓位
+    pub pos_ccy: String,
+    /// 可平仓数量，适用于 币币杠杆,交割/永续（开平仓模式），期权（交易账户及保证金账户逐仓）。
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub avail_pos: Option<Decimal>,
+    /// 开仓平均价
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub avg_px: Option<Decimal>,
+    /// 未实现收益（以标记价格计算）
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub upl: Option<Decimal>,
+    /// 未实现收益率（以标记价格计算）
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub upl_ratio: Option<Decimal>,
+    /// 以最新成交价格计算的未实现收益
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub upl_last_px: Option<Decimal>,
+    /// 杠杆倍数，不适用于期权
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub lever: Option<Decimal>,
+    /// 预估强平价，不适用于期权
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub liq_px: Option<Decimal>,
+    /// 最新标记价格
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub mark_px: Option<Decimal>,
+    /// 初始保证金，仅适用于全仓
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub imr: Option<Decimal>,
+    /// 保证金余额，可增减，仅适用于逐仓
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub margin: Option<Decimal>,
+    /// 保证金率
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub mgn_ratio: Option<Decimal>,
+    /// 维持保证金
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub mmr: Option<Decimal>,
+    /// 负债额，仅适用于币币杠杆
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub liab: Option<Decimal>,
+    /// 负债币种，仅适用于币币杠杆
+    #[serde(default)]
+    pub liab_ccy: String,
+    /// 利息，已经生成的未扣利息
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub interest: Option<Decimal>,
+    /// 最新成交ID
+    #[serde(default)]
+    pub trade_id: String,
+    /// 期权市值，仅适用于期权
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub opt_val: Option<Decimal>,
+    /// 以美金价值为单位的持仓数量
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub notional_usd: Option<Decimal>,
+    /// 自动减仓信号区，分为5档，从1到5，数字越小代表adl强度越弱
+    #[serde(default)]
+    pub adl: String,
+    /// 占用保证金的币种
+    pub ccy: String,
+    /// 最新成交价
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub last: Option<Decimal>,
+    /// 最新指数价格
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub idx_px: Option<Decimal>,
+    /// 盈亏平衡价
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub be_px: Option<Decimal>,
+    /// 已实现收益
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub realized_pnl: Option<Decimal>,
+    /// 平仓订单累计收益额
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub pnl: Option<Decimal>,
+    /// 累计手续费金额，正数代表平台返佣 ，负数代表平台扣除
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub fee: Option<Decimal>,
+    /// 累计资金费用
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub funding_fee: Option<Decimal>,
+    /// 累计爆仓罚金，为负数
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub liq_penalty: Option<Decimal>,
+    /// 美金本位持仓仓位delta，仅适用于期权
+    #[serde(rename = "deltaBS", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub delta_bs: Option<Decimal>,
+    /// 币本位持仓仓位delta，仅适用于期权
+    #[serde(rename = "deltaPA", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub delta_pa: Option<Decimal>,
+    /// 美金本位持仓仓位gamma，仅适用于期权
+    #[serde(rename = "gammaBS", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub gamma_bs: Option<Decimal>,
+    /// 币本位持仓仓位gamma，仅适用于期权
+    #[serde(rename = "gammaPA", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub gamma_pa: Option<Decimal>,
+    /// 美金本位持仓仓位theta，仅适用于期权
+    #[serde(rename = "thetaBS", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub theta_bs: Option<Decimal>,
+    /// 币本位持仓仓位theta，仅适用于期权
+    #[serde(rename = "thetaPA", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub theta_pa: Option<Decimal>,
+    /// 美金本位持仓仓位vega，仅适用于期权
+    #[serde(rename = "vegaBS", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub vega_bs: Option<Decimal>,
+    /// 币本位持仓仓位vega，仅适用于期权
+    #[serde(rename = "vegaPA", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub vega_pa: Option<Decimal>,
+    /// 持仓创建时间，Unix时间戳的毫秒数格式
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub c_time: i64,
+    /// 最近一次持仓更新时间，Unix时间戳的毫秒数格式
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub u_time: i64,
 }
 
 // 查看历史持仓信息
@@ -78,8 +249,8 @@ pub struct AccountPositionsHistory {
     #[serde(rename = "type")]
     pub ptype: String, //持仓方向
 
-    #[serde(deserialize_with = "de_float_from_str")]
-    pub pnl: f32, // 平仓收益额
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub pnl: Decimal, // 平仓收益额
 
                   // #[serde(deserialize_with = "de_float_from_str")]
                   // // 持仓数量，逐仓自主划转模式下，转入保证金后会产生pos为0的仓位
@@ -97,6 +268,145 @@ pub struct AccountSetLeverage {
     pub pos_side: String,
 }
 
+/// 最大可下单数量
+/// GET /api/v5/account/max-size
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaxSize {
+    pub inst_id: String,
+    /// 保证金币种
+    #[serde(default)]
+    pub ccy: String,
+    /// 最大可买，币币为交易货币数量，币币杠杆和合约为张数
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub max_buy: Decimal,
+    /// 最大可卖，币币为计价货币数量，币币杠杆和合约为张数
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub max_sell: Decimal,
+}
+
+impl MaxSize {
+    pub fn max(&self, side: TradeSide) -> Decimal {
+        match side {
+            TradeSide::Buy => self.max_buy,
+            TradeSide::Sell => self.max_sell,
+        }
+    }
+}
+
+/// 最大可用余额/保证金
+/// GET /api/v5/account/max-avail-size
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaxAvailSize {
+    pub inst_id: String,
+    /// 最大买入可用余额/保证金
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub avail_buy: Decimal,
+    /// 最大卖出可用余额/保证金
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub avail_sell: Decimal,
+}
+
+impl MaxAvailSize {
+    pub fn avail(&self, side: TradeSide) -> Decimal {
+        match side {
+            TradeSide::Buy => self.avail_buy,
+            TradeSide::Sell => self.avail_sell,
+        }
+    }
+}
+
+/// 交易产品最大可借
+/// GET /api/v5/account/max-loan
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaxLoan {
+    #[serde(default)]
+    pub inst_id: String,
+    pub mgn_mode: TradeMode,
+    /// 保证金币种
+    #[serde(default)]
+    pub mgn_ccy: String,
+    /// 最大可借
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub max_loan: Decimal,
+    /// 币种
+    pub ccy: String,
+    /// 订单方向
+    pub side: TradeSide,
+}
+
+/// 当前账户交易手续费费率
+/// 正数代表返佣，负数代表平台扣除的手续费
+/// GET /api/v5/account/trade-fee
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeFee {
+    pub inst_type: InstType,
+    /// 手续费等级，如 Lv1
+    pub level: String,
+    /// 吃单手续费率，币本位合约、币币和币币杠杆使用
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub taker: Decimal,
+    /// 挂单手续费率，币本位合约、币币和币币杠杆使用
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub maker: Decimal,
+    /// USDT合约吃单手续费率
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub taker_u: Option<Decimal>,
+    /// USDT合约挂单手续费率
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub maker_u: Option<Decimal>,
+    /// USDC交易吃单手续费率
+    #[serde(default, rename = "takerUSDC", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub taker_usdc: Option<Decimal>,
+    /// USDC交易挂单手续费率
+    #[serde(default, rename = "makerUSDC", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub maker_usdc: Option<Decimal>,
+    /// 交割手续费率
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub delivery: Option<Decimal>,
+    /// 行权手续费率
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub exercise: Option<Decimal>,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub ts: i64,
+}
+
+impl TradeFee {
+    /// 按结算币种选择费率，settle_ccy为USDT、USDC时使用对应的费率，没有时使用taker/maker
+    pub fn fee_rate(&self, exec_type: ExecuteType, settle_ccy: &str) -> Decimal {
+        let (taker, maker) = match settle_ccy {
+            "USDT" => (self.taker_u, self.maker_u),
+            "USDC" => (self.taker_usdc, self.maker_usdc),
+            _ => (None, None),
+        };
+        match exec_type {
+            ExecuteType::Maker => maker.unwrap_or(self.maker),
+            _ => taker.unwrap_or(self.taker),
+        }
+    }
+
+    /// 预估手续费，notional为成交金额，返回值为负数时代表扣除的手续费
+    pub fn expected_fee(&self, notional: Decimal, exec_type: ExecuteType, settle_ccy: &str) -> Decimal {
+        notional * self.fee_rate(exec_type, settle_ccy)
+    }
+}
+
+/// 杠杆倍数
+/// GET /api/v5/account/leverage-info
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeverageInfo {
+    pub inst_id: String,
+    pub mgn_mode: TradeMode,
+    /// 持仓方向，开平仓模式下的逐仓交割/永续为long或short，其他为net
+    pub pos_side: PositionSide,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub lever: Decimal,
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Instrument {
     /// 产品类型
@@ -120,11 +430,11 @@ pub struct Instrument {
     #[serde(rename = "settleCcy")]
     pub settle_ccy: String,
     /// 合约面值，仅适用于交割/永续/期权
-    #[serde(rename = "ctVal")]
-    pub ct_val: String,
+    #[serde(rename = "ctVal", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub ct_val: Option<Decimal>,
     /// 合约乘数，仅适用于交割/永续/期权
-    #[serde(rename = "ctMult")]
-    pub ct_mult: String,
+    #[serde(rename = "ctMult", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub ct_mult: Option<Decimal>,
     /// 合约面值计价币种，仅适用于交割/永续/期权
     #[serde(rename = "ctValCcy")]
     pub ct_val_ccy: String,
@@ -132,7 +442,8 @@ pub struct Instrument {
     #[serde(rename = "optType")]
     pub opt_type: String,
     /// 行权价格，仅适用于期权
-    pub stk: String,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub stk: Option<Decimal>,
     /// 上线日期
     /// Unix时间戳的毫秒数格式，如 1597026383085
     #[serde(rename = "listTime", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
@@ -146,15 +457,15 @@ pub struct Instrument {
     pub lever: String,
     /// 下单价格精度，如 0.0001
     /// 对于期权来说，是梯度中的最小下单价格精度，如果想要获取期权价格梯度，请使用"获取期权价格梯度"接口
-    #[serde(rename = "tickSz")]
-    pub tick_sz: String,
+    #[serde(rename = "tickSz", serialize_with="to_str",deserialize_with="from_str")]
+    pub tick_sz: Decimal,
     /// 下单数量精度，如 BTC-USDT-SWAP：1
-    #[serde(rename = "lotSz")]
-    pub lot_sz: String,
+    #[serde(rename = "lotSz", serialize_with="to_str",deserialize_with="from_str")]
+    pub lot_sz: Decimal,
     /// 最小下单数量,
     /// 合约的数量单位是“张”，现货的数量单位是“交易货币”
-    #[serde(rename = "minSz")]
-    pub min_sz: String,
+    #[serde(rename = "minSz", serialize_with="to_str",deserialize_with="from_str")]
+    pub min_sz: Decimal,
     /// linear：正向合约
     /// inverse：反向合约
     /// 仅适用于交割/永续
@@ -175,28 +486,28 @@ pub struct Instrument {
     pub state: InstrumentState,
     /// 合约或现货限价单的单笔最大委托数量,
     /// 合约的数量单位是“张”，现货的数量单位是“交易货币”
-    #[serde(rename = "maxLmtSz")]
-    pub max_lmt_sz: String,
+    #[serde(rename = "maxLmtSz", serialize_with="to_str",deserialize_with="from_str")]
+    pub max_lmt_sz: Decimal,
     /// 合约或现货市价单的单笔最大委托数量,
     /// 合约的数量单位是“张”，现货的数量单位是“USDT”
-    #[serde(rename = "maxMktSz")]
-    pub max_mkt_sz: String,
+    #[serde(rename = "maxMktSz", serialize_with="to_str",deserialize_with="from_str")]
+    pub max_mkt_sz: Decimal,
     /// 合约或现货时间加权单的单笔最大委托数量,
     /// 合约的数量单位是“张”，现货的数量单位是“交易货币”
-    #[serde(rename = "maxTwapSz")]
-    pub max_twap_sz: String,
+    #[serde(rename = "maxTwapSz", serialize_with="to_str",deserialize_with="from_str")]
+    pub max_twap_sz: Decimal,
     /// 合约或现货冰山委托的单笔最大委托数量,
     /// 合约的数量单位是“张”，现货的数量单位是“交易货币”
-    #[serde(rename = "maxIcebergSz")]
-    pub max_iceberg_sz: String,
+    #[serde(rename = "maxIcebergSz", serialize_with="to_str",deserialize_with="from_str")]
+    pub max_iceberg_sz: Decimal,
     /// 合约或现货计划委托委托的单笔最大委托数量,
     /// 合约的数量单位是“张”，现货的数量单位是“交易货币”
-    #[serde(rename = "maxTriggerSz")]
-    pub max_trigger_sz: String,
+    #[serde(rename = "maxTriggerSz", serialize_with="to_str",deserialize_with="from_str")]
+    pub max_trigger_sz: Decimal,
     /// 合约或现货止盈止损市价委托的单笔最大委托数量,
     /// 合约的数量单位是“张”，现货的数量单位是“USDT”
-    #[serde(rename = "maxStopSz")]
-    pub max_stop_sz: String,
+    #[serde(rename = "maxStopSz", serialize_with="to_str",deserialize_with="from_str")]
+    pub max_stop_sz: Decimal,
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
@@ -225,20 +536,30 @@ pub enum InstrumentState {
 pub struct MarketTickers {
     pub inst_type: String,
     pub inst_id: String,
-    pub last: String,
-    #[serde(deserialize_with = "de_float_from_str")]
-    pub ask_px: f32,
-    pub ask_sz: String,
-    #[serde(deserialize_with = "de_float_from_str")]
-    pub bid_px: f32,
-    pub bid_sz: String,
-    pub open24h: String,
-    pub high24h: String,
-    pub low24h: String,
-    pub vol_ccy24h: String,
-    pub vol24h: String,
-    pub sod_utc0: String,
-    pub sod_utc8: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub last: Decimal,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub ask_px: Option<Decimal>,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub ask_sz: Option<Decimal>,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub bid_px: Option<Decimal>,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub bid_sz: Option<Decimal>,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub open24h: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub high24h: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub low24h: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub vol_ccy24h: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub vol24h: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub sod_utc0: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub sod_utc8: Decimal,
     pub ts: String,
 }
 
@@ -249,20 +570,30 @@ pub struct MarketTickers {
 pub struct MarketTicker {
     pub inst_type: String,
     pub inst_id: String,
-    pub last: String,
-    #[serde(deserialize_with = "de_float_from_str")]
-    pub ask_px: f32,
-    pub ask_sz: String,
-    #[serde(deserialize_with = "de_float_from_str")]
-    pub bid_px: f32,
-    pub bid_sz: String,
-    pub open24h: String,
-    pub high24h: String,
-    pub low24h: String,
-    pub vol_ccy24h: String,
-    pub vol24h: String,
-    pub sod_utc0: String,
-    pub sod_utc8: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub last: Decimal,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub ask_px: Option<Decimal>,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub ask_sz: Option<Decimal>,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub bid_px: Option<Decimal>,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub bid_sz: Option<Decimal>,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub open24h: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub high24h: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub low24h: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub vol_ccy24h: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub vol24h: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub sod_utc0: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub sod_utc8: Decimal,
     #[serde(serialize_with="to_str",deserialize_with="from_str")]
     pub ts: i64,
 }
@@ -279,88 +610,144 @@ pub struct MarketBooks {
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MarketBooksItemData {
-    #[serde(deserialize_with = "de_float_from_str")]
-    pub price: f32,
-    #[serde(deserialize_with = "de_float_from_str")]
-    pub sz: f32,
-    #[serde(deserialize_with = "de_float_from_str")]
-    pub ignore: f32,
-    #[serde(deserialize_with = "de_float_from_str")]
-    pub count: f32,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub price: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub sz: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub ignore: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub count: u32,
 }
 
-// 获取未成交订单列表
-// 获取当前账户下所有未成交订单信息
-// GET /api/v5/trade/orders-pending
+/// 订单详情，查订单信息、未成交订单列表、历史订单记录使用相同的结构
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct TradeOrdersPending {
+pub struct TradeOrderGet {
+    /// 产品类型
     pub inst_type: InstType,
+    /// 产品ID
     pub inst_id: String,
+    /// 币币市价单委托数量sz的单位 base_ccy: 交易货币 quote_ccy：计价货币
     pub tgt_ccy: String,
+    /// 保证金币种，仅适用于单币种保证金模式下的全仓币币杠杆订单
     pub ccy: String,
+    /// 订单ID
     pub ord_id: String,
+    /// 客户自定义订单ID
     pub cl_ord_id: String,
+    /// 订单标签
     pub tag: String,
-    pub px: String,
-    pub sz: String,
-    pub pnl: String,
+    /// 委托价格，市价单为空
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub px: Option<Decimal>,
+    /// 委托数量
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub sz: Decimal,
+    /// 收益，适用于有成交的平仓订单，其他情况均为0
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub pnl: Decimal,
+    /// 订单类型
     pub ord_type: OrderType,
+    /// 订单方向
     pub side: TradeSide,
+    /// 持仓方向
     pub pos_side: PositionSide,
+    /// 交易模式
     pub td_mode: TradeMode,
-    pub acc_fill_sz: String,
-
-    pub fill_px: String,
-    #[serde(rename = "tradeId", serialize_with="to_str",deserialize_with="from_str")]
+    /// 累计成交数量
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub acc_fill_sz: Decimal,
+    /// 最新成交价格，如果成交数量为0，该字段为空
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub fill_px: Option<Decimal>,
+    /// 最新成交ID
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
     pub trade_id: i64,
-    pub fill_sz: String,
-    #[serde(rename = "fillTime", serialize_with="to_str",deserialize_with="from_str")]
+    /// 最新成交数量
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub fill_sz: Option<Decimal>,
+    /// 最新成交时间
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
     pub fill_time: i64,
-    pub avg_px: String,
+    /// 成交均价，如果成交数量为0，该字段为空
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub avg_px: Option<Decimal>,
+    /// 订单状态
     pub state: OrderState,
-
-    pub lever: String,
-    pub tp_trigger_px: String,
+    /// 杠杆倍数，仅适用于币币杠杆/交割/永续
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub lever: Option<Decimal>,
+    /// 下单附带止盈止损时，客户自定义的策略订单ID
+    #[serde(default)]
+    pub attach_algo_cl_ord_id: String,
+    /// 止盈触发价
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub tp_trigger_px: Option<Decimal>,
+    /// 止盈触发价类型
     pub tp_trigger_px_type: TpTriggerPxType,
-    pub sl_trigger_px: String,
+    /// 止盈委托价
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub tp_ord_px: Option<Decimal>,
+    /// 止损触发价
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub sl_trigger_px: Option<Decimal>,
+    /// 止损触发价类型
     pub sl_trigger_px_type: TpTriggerPxType,
-
-    pub sl_ord_px: String,
-    pub tp_ord_px: String,
+    /// 止损委托价
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub sl_ord_px: Option<Decimal>,
+    /// 自成交保护ID
+    pub stp_id: String,
+    /// 自成交保护模式
+    pub stp_mode: StopMode,
+    /// 交易手续费币种
     pub fee_ccy: String,
-    pub fee: String,
+    /// 订单交易累计的手续费与返佣，负数代表平台扣除的手续费
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub fee: Decimal,
+    /// 返佣金币种
     pub rebate_ccy: String,
+    /// 返佣累计金额，仅适用于币币和杠杆
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub rebate: Decimal,
+    /// 订单来源
     pub source: String,
-    pub rebate: String,
+    /// 订单种类 normal：普通委托 twap：TWAP自动换币 adl：ADL自动减仓 等
     pub category: String,
-
-    pub stp_id: String,
-    pub stp_mode: StopMode,
-
-    #[serde(rename = "reduceOnly", serialize_with="to_str",deserialize_with="from_str")]
+    /// 是否只减仓
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
     pub reduce_only: bool,
-    pub quick_mgn_type: String,
+    /// 订单取消的来源
+    #[serde(default)]
+    pub cancel_source: String,
+    /// 订单取消的来源描述
+    #[serde(default)]
+    pub cancel_source_reason: String,
+    /// 一键借币类型，仅适用于杠杆逐仓的一键借币模式
+    pub quick_mgn_type: QuickMgnType,
+    /// 客户自定义策略订单ID，策略订单触发时有值
+    #[serde(default)]
+    pub algo_cl_ord_id: String,
+    /// 策略委托单ID，策略订单触发时有值
+    #[serde(default)]
+    pub algo_id: String,
+    /// 订单状态更新时间，Unix时间戳的毫秒数格式
     #[serde(serialize_with="to_str",deserialize_with="from_str")]
     pub u_time: i64,
+    /// 订单创建时间，Unix时间戳的毫秒数格式
     #[serde(serialize_with="to_str",deserialize_with="from_str")]
     pub c_time: i64,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct TradeOrdersHistory {
-    pub inst_type: String,
-    pub inst_id: String,
+// 获取未成交订单列表
+// 获取当前账户下所有未成交订单信息
+// GET /api/v5/trade/orders-pending
+pub type TradeOrdersPending = TradeOrderGet;
 
-    pub ord_type: OrderType,
-    //     订单状态
-    // canceled：撤单成功
-    // filled：完全成交
-    pub state: OrderState,
-    #[serde(deserialize_with = "de_float_from_str")]
-    pub pnl: f32,
-}
+// 获取历史订单记录（近七天）
+// GET /api/v5/trade/orders-history
+pub type TradeOrdersHistory = TradeOrderGet;
 
 // 批量撤单
 // 撤销未完成的订单，每次最多可以撤销20个订单。请求参数应该按数组格式传递。
@@ -401,6 +788,22 @@ pub struct TradeAmendOrder {
     pub s_msg: String,  //持仓方向
 }
 
+pub type TradeCancelOrder = TradeCancelBatchOrders;
+pub type TradeAmendBatchOrders = TradeAmendOrder;
+
+// 市价仓位全平
+// POST /api/v5/trade/close-position
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeClosePosition {
+    pub inst_id: String,
+    pub pos_side: PositionSide,
+    #[serde(default)]
+    pub cl_ord_id: String,
+    #[serde(default)]
+    pub tag: String,
+}
+
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
 pub enum TradeMode {
     /// isolated：逐仓
@@ -562,10 +965,11 @@ pub struct OrderRequestInfo {
     #[serde(rename="ordType")]
     pub order_type: OrderType,
     /// 委托数量
-    pub sz: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub sz: Decimal,
     /// 委托价格，仅适用于limit、post_only、fok、ioc、mmp类型的订单
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub px: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub px: Option<Decimal>,
     /// 是否只减仓，true 或 false，默认false
     /// 仅适用于币币杠杆，以及买卖模式下的交割/永续
     /// 仅适用于单币种保证金模式和跨币种保证金模式
@@ -586,18 +990,18 @@ pub struct OrderRequestInfo {
     #[serde(rename="attachAlgoClOrdId", skip_serializing_if = "Option::is_none")]
     pub attach_algo_cl_ord_id: Option<String>,
     /// 止盈触发价，如果填写此参数，必须填写 止盈委托价
-    #[serde(rename="tpTriggerPx", skip_serializing_if = "Option::is_none")]
-    pub tp_trigger_px: Option<String>,
+    #[serde(rename="tpTriggerPx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub tp_trigger_px: Option<Decimal>,
     /// 止盈委托价，如果填写此参数，必须填写 止盈触发价
     /// 委托价格为-1时，执行市价止盈
-    #[serde(rename="tpOrdPx", skip_serializing_if = "Option::is_none")]
-    pub tp_ord_px: Option<String>,
-    #[serde(rename="slTriggerPx", skip_serializing_if = "Option::is_none")]
-    pub sl_trigger_px: Option<String>,
+    #[serde(rename="tpOrdPx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub tp_ord_px: Option<Decimal>,
+    #[serde(rename="slTriggerPx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub sl_trigger_px: Option<Decimal>,
     /// 止损委托价，如果填写此参数，必须填写 止损触发价
     /// 委托价格为-1时，执行市价止损
-    #[serde(rename="slOrdPx", skip_serializing_if = "Option::is_none")]
-    pub sl_ord_px: Option<String>,
+    #[serde(rename="slOrdPx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub sl_ord_px: Option<Decimal>,
     /// 自成交保护ID。来自同一个母账户配着同一个ID的订单不能自成交
     /// 用户自定义1<=x<=999999999的整数
     #[serde(rename="stpId", skip_serializing_if = "Option::is_none")]
@@ -620,7 +1024,7 @@ pub struct OrderRequestInfo {
     /// index：指数价格
     /// mark：标记价格
     /// 默认为last
-    #[serde(rename="tpTriggerPxType", skip_serializing_if = "Option::is_none")]
+    #[serde(rename="slTriggerPxType", skip_serializing_if = "Option::is_none")]
     pub sl_trigger_px_type: Option<TpTriggerPxType>,
     /// 一键借币类型，仅适用于杠杆逐仓的一键借币模式：
     /// manual：手动，auto_borrow： 自动借币，auto_repay： 自动还币
@@ -630,7 +1034,7 @@ pub struct OrderRequestInfo {
 }
 
 impl OrderRequestInfo {
-    pub fn new_spot_limit_order(inst_id: &str, side: TradeSide, sz: String, px: String , cl_ord_id: Option<String>, tag: Option<String>) -> Self {
+    pub fn new_spot_limit_order(inst_id: &str, side: TradeSide, sz: Decimal, px: Decimal, cl_ord_id: Option<String>, tag: Option<String>) -> Self {
         Self {
             inst_id: inst_id.to_string(),
             td_mode: TradeMode::Cash,
@@ -657,99 +1061,450 @@ impl OrderRequestInfo {
             quick_mgn_type: None,
         }
     }
+
+    /// 创建下单请求的构建器，默认为市价单
+    pub fn builder(inst_id: &str, td_mode: TradeMode, side: TradeSide, sz: Decimal) -> OrderRequestBuilder {
+        let mut order = Self::new_spot_limit_order(inst_id, side, sz, Decimal::ZERO, None, None);
+        order.td_mode = td_mode;
+        order.order_type = OrderType::Market;
+        order.px = None;
+        OrderRequestBuilder {
+            order,
+            long_short_mode: None,
+        }
+    }
+
+    /// 根据产品ID和交易模式推断产品类型，币币和币币杠杆通过交易模式区分
+    /// 币币全仓无法确定类型时返回None，跨币种和组合保证金模式下可能是币币，也可能是币币杠杆
+    pub fn inst_type(&self) -> Option<InstType> {
+        let parts: Vec<&str> = self.inst_id.split('-').collect();
+        match parts.as_slice() {
+            [.., "SWAP"] => Some(InstType::Swap),
+            [_, _, _, _, "C" | "P"] => Some(InstType::Option),
+            [_, _, _] => Some(InstType::Futures),
+            _ => match self.td_mode {
+                TradeMode::Cash => Some(InstType::Spot),
+                TradeMode::Isolated => Some(InstType::Margin),
+                TradeMode::Cross => None,
+            },
+        }
+    }
+
+    /// 检查各字段的组合是否符合产品类型和订单类型的要求，不符合时返回InvalidOrder错误
+    /// 产品类型无法确定时，只检查币币和币币杠杆都不允许的组合
+    pub fn validate(&self) -> Result<(), OkxError> {
+        let invalid = |msg: &str| Err(OkxError::InvalidOrder(format!("{} {}", self.inst_id, msg)));
+        let inst_type = self.inst_type();
+        let is_derivative = matches!(inst_type, Some(InstType::Swap | InstType::Futures | InstType::Option));
+
+        if self.sz <= Decimal::ZERO {
+            return invalid("sz must be positive");
+        }
+        match self.order_type {
+            OrderType::Market | OrderType::OptimalLimitIoc => {
+                if self.px.is_some() {
+                    return invalid("px is not allowed for market order");
+                }
+            }
+            _ => {
+                if !matches!(self.px, Some(px) if px > Decimal::ZERO) {
+                    return invalid("px is required for limit order");
+                }
+            }
+        }
+        if self.order_type == OrderType::OptimalLimitIoc && !matches!(inst_type, Some(InstType::Swap | InstType::Futures)) {
+            return invalid("optimal_limit_ioc is only for futures and swap");
+        }
+        if matches!(self.order_type, OrderType::Mmp | OrderType::MmpAndPostOnly) && inst_type != Some(InstType::Option) {
+            return invalid("mmp order is only for option");
+        }
+        if is_derivative && self.td_mode == TradeMode::Cash {
+            return invalid("cash trade mode is not allowed for derivatives");
+        }
+        if matches!(self.pos_side, Some(PositionSide::Long | PositionSide::Short)) && !matches!(inst_type, Some(InstType::Swap | InstType::Futures)) {
+            return invalid("long/short pos_side is only for futures and swap");
+        }
+        if self.reduce_only == Some(true) && inst_type == Some(InstType::Spot) {
+            return invalid("reduce_only is not allowed for spot");
+        }
+        if (self.tgt_ccy.is_some() || self.ban_amend.is_some()) && !(matches!(inst_type, Some(InstType::Spot) | None) && self.order_type == OrderType::Market) {
+            return invalid("tgt_ccy and ban_amend are only for spot market order");
+        }
+        if self.tp_trigger_px.is_some() != self.tp_ord_px.is_some() {
+            return invalid("tp_trigger_px and tp_ord_px must be set together");
+        }
+        if self.sl_trigger_px.is_some() != self.sl_ord_px.is_some() {
+            return invalid("sl_trigger_px and sl_ord_px must be set together");
+        }
+        if (self.tp_trigger_px_type.is_some() && self.tp_trigger_px.is_none())
+            || (self.sl_trigger_px_type.is_some() && self.sl_trigger_px.is_none()) {
+            return invalid("trigger px type is set without trigger px");
+        }
+        if self.stp_mode == Some(StopMode::CancelBoth) && self.order_type == OrderType::Fok {
+            return invalid("cancel_both stp_mode is not allowed for fok order");
+        }
+        if self.quick_mgn_type.is_some() && inst_type != Some(InstType::Margin) {
+            return invalid("quick_mgn_type is only for isolated margin");
+        }
+        Ok(())
+    }
 }
 
-/// 产品类型
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
-pub enum InstType {
-    /// 币币
-    #[serde(rename="SPOT")]
-    Spot,
-    /// 币币杠杆
-    #[serde(rename="MARGIN")]
-    Margin,
-    /// 永续合约
-    #[serde(rename="SWAP")]
-    Swap,
-    /// 交割合约
-    #[serde(rename="FUTURES")]
-    Futures,
-    /// 期权
-    #[serde(rename="OPTION")]
-    Option,
-    /// 所有
-    #[serde(rename="ANY")]
-    Any,
+/// 下单请求的构建器，build时会检查字段组合
+#[derive(Clone, Debug)]
+pub struct OrderRequestBuilder {
+    order: OrderRequestInfo,
+    /// 账户的持仓方式，为None时不检查pos_side
+    long_short_mode: Option<bool>,
 }
 
-// 获取订单信息
-// 查订单信息
+impl OrderRequestBuilder {
+    /// 市价单
+    pub fn market(mut self) -> Self {
+        self.order.order_type = OrderType::Market;
+        self.order.px = None;
+        self
+    }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct TradeOrderGet {
-    pub state: String, //订单状态  filled
-}
+    /// 限价单
+    pub fn limit(self, px: Decimal) -> Self {
+        self.with_px(OrderType::Limit, px)
+    }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct BalanceDetailItem {
-    /// 币种
-    pub ccy: String,
-    /// 币种总权益
-    pub eq: String,
-    /// 币种余额
-    #[serde(rename = "cashBal")]
-    pub cash_bal: String,
-    #[serde(rename = "uTime", serialize_with="to_str",deserialize_with="from_str")]
-    pub u_time: i64,
-    /// 币种逐仓仓位权益
-    /// 适用于单币种保证金模式和跨币种保证金模式和组合保证金模式
-    #[serde(rename = "isoEq")]
-    pub iso_eq: String,
-    /// 可用保证金
-    /// 适用于单币种保证金模式和跨币种保证金模式和组合保证金模式
-    #[serde(rename = "availEq")]
-    pub avail_eq: String,
-    /// 美金层面币种折算权益
-    #[serde(rename = "disEq")]
-    pub dis_eq: String,
-    /// 可用余额
-    /// 适用于简单交易模式、单币种保证金模式、跨币种保证金模式和组合保证金模式
-    #[serde(rename = "availBal")]
-    pub avail_bal: String,
-    /// 币种占用金额
-    #[serde(rename = "frozenBal")]
-    pub frozen_bal: String,
-    /// 挂单冻结数量
-    #[serde(rename = "ordFrozen")]
-    pub ord_frozen: String,
-    /// 币种负债额
-    /// 适用于跨币种保证金模式和组合保证金模式
-    pub liab: String,
-    /// 未实现盈亏
-    /// 适用于单币种保证金模式和跨币种保证金模式和组合保证金模式
-    pub upl: String,
-    /// 由于仓位未实现亏损导致的负债
-    /// 适用于跨币种保证金模式和组合保证金模式
-    #[serde(rename = "uplLiab")]
-    pub upl_liab: String,
-    /// 币种全仓负债额
-    /// 适用于跨币种保证金模式和组合保证金模式
-    #[serde(rename = "crossLiab")]
-    pub cross_liab: String,
-    /// 币种逐仓负债额
-    /// 适用于跨币种保证金模式和组合保证金模式
-    #[serde(rename = "isoLiab")]
-    pub iso_liab: String,
-    /// 保证金率
-    /// 适用于单币种保证金模式
-    #[serde(rename = "mgnRatio")]
-    pub mgn_ratio: String,
-    #[serde(rename = "eqUsd")]
-    pub eq_usd: String,
-    /// 计息，应扣未扣利息。
+    /// 只做maker单
+    pub fn post_only(self, px: Decimal) -> Self {
+        self.with_px(OrderType::PostOnly, px)
+    }
+
+    /// 全部成交或立即取消
+    pub fn fok(self, px: Decimal) -> Self {
+        self.with_px(OrderType::Fok, px)
+    }
+
+    /// 立即成交并取消剩余
+    pub fn ioc(self, px: Decimal) -> Self {
+        self.with_px(OrderType::Ioc, px)
+    }
+
+    /// 市价委托立即成交并取消剩余，仅适用交割、永续
+    pub fn optimal_limit_ioc(mut self) -> Self {
+        self.order.order_type = OrderType::OptimalLimitIoc;
+        self.order.px = None;
+        self
+    }
+
+    fn with_px(mut self, order_type: OrderType, px: Decimal) -> Self {
+        self.order.order_type = order_type;
+        self.order.px = Some(px);
+        self
+    }
+
+    pub fn pos_side(mut self, pos_side: PositionSide) -> Self {
+        self.order.pos_side = Some(pos_side);
+        self
+    }
+
+    /// 账户的持仓方式，true为开平仓模式，false为买卖模式
+    /// 设置后，交割和永续在开平仓模式下必须设置long或short的pos_side
+    pub fn long_short_mode(mut self, long_short_mode: bool) -> Self {
+        self.long_short_mode = Some(long_short_mode);
+        self
+    }
+
+    pub fn reduce_only(mut self) -> Self {
+        self.order.reduce_only = Some(true);
+        self
+    }
+
+    pub fn ccy(mut self, ccy: impl Into<String>) -> Self {
+        self.order.ccy = Some(ccy.into());
+        self
+    }
+
+    pub fn cl_ord_id(mut self, cl_ord_id: impl Into<String>) -> Self {
+        self.order.cl_ord_id = Some(cl_ord_id.into());
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.order.tag = Some(tag.into());
+        self
+    }
+
+    /// 币币市价单委托数量sz的单位 base_ccy: 交易货币 quote_ccy：计价货币
+    pub fn tgt_ccy(mut self, tgt_ccy: impl Into<String>) -> Self {
+        self.order.tgt_ccy = Some(tgt_ccy.into());
+        self
+    }
+
+    /// 余额不足时不改单，仅适用于币币市价单
+    pub fn ban_amend(mut self) -> Self {
+        self.order.ban_amend = Some(true);
+        self
+    }
+
+    /// 附带止盈，ord_px为-1时执行市价止盈
+    pub fn take_profit(mut self, trigger_px: Decimal, ord_px: Decimal, trigger_px_type: Option<TpTriggerPxType>) -> Self {
+        self.order.tp_trigger_px = Some(trigger_px);
+        self.order.tp_ord_px = Some(ord_px);
+        self.order.tp_trigger_px_type = trigger_px_type;
+        self
+    }
+
+    /// 附带止损，ord_px为-1时执行市价止损
+    pub fn stop_loss(mut self, trigger_px: Decimal, ord_px: Decimal, trigger_px_type: Option<TpTriggerPxType>) -> Self {
+        self.order.sl_trigger_px = Some(trigger_px);
+        self.order.sl_ord_px = Some(ord_px);
+        self.order.sl_trigger_px_type = trigger_px_type;
+        self
+    }
+
+    /// 附带止盈止损时，客户自定义的策略订单ID
+    pub fn attach_algo_cl_ord_id(mut self, attach_algo_cl_ord_id: impl Into<String>) -> Self {
+        self.order.attach_algo_cl_ord_id = Some(attach_algo_cl_ord_id.into());
+        self
+    }
+
+    /// 自成交保护
+    pub fn self_trade_prevention(mut self, stp_id: Option<String>, stp_mode: StopMode) -> Self {
+        self.order.stp_id = stp_id;
+        self.order.stp_mode = Some(stp_mode);
+        self
+    }
+
+    pub fn quick_mgn_type(mut self, quick_mgn_type: QuickMgnType) -> Self {
+        self.order.quick_mgn_type = Some(quick_mgn_type);
+        self
+    }
+
+    pub fn build(self) -> Result<OrderRequestInfo, OkxError> {
+        let order = self.order;
+        order.validate()?;
+
+        if let Some(long_short_mode) = self.long_short_mode {
+            let has_long_short = matches!(order.pos_side, Some(PositionSide::Long | PositionSide::Short));
+            let need_long_short = long_short_mode && matches!(order.inst_type(), Some(InstType::Swap | InstType::Futures));
+            if need_long_short != has_long_short {
+                return Err(OkxError::InvalidOrder(format!("{} pos_side must be long or short only in long/short mode", order.inst_id)));
+            }
+        }
+        Ok(order)
+    }
+}
+
+/// 撤单请求，ordId和clOrdId必须传一个，若传两个，以ordId为主
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CancelOrderRequest {
+    /// 产品ID，如 BTC-USDT
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    #[serde(rename = "ordId", skip_serializing_if = "Option::is_none")]
+    pub ord_id: Option<String>,
+    /// 客户自定义订单ID
+    #[serde(rename = "clOrdId", skip_serializing_if = "Option::is_none")]
+    pub cl_ord_id: Option<String>,
+}
+
+impl CancelOrderRequest {
+    pub fn with_ord_id(inst_id: &str, ord_id: &str) -> Self {
+        Self {
+            inst_id: inst_id.to_string(),
+            ord_id: Some(ord_id.to_string()),
+            cl_ord_id: None,
+        }
+    }
+
+    pub fn with_cl_ord_id(inst_id: &str, cl_ord_id: &str) -> Self {
+        Self {
+            inst_id: inst_id.to_string(),
+            ord_id: None,
+            cl_ord_id: Some(cl_ord_id.to_string()),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), OkxError> {
+        if self.ord_id.is_none() && self.cl_ord_id.is_none() {
+            return Err(OkxError::InvalidOrder(format!("{} ord_id or cl_ord_id is required", self.inst_id)));
+        }
+        Ok(())
+    }
+}
+
+/// 撤销策略委托请求
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CancelAlgoRequest {
+    /// 策略委托单ID
+    #[serde(rename = "algoId")]
+    pub algo_id: String,
+    /// 产品ID，如 BTC-USDT
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+}
+
+impl CancelAlgoRequest {
+    pub fn new(inst_id: &str, algo_id: &str) -> Self {
+        Self {
+            algo_id: algo_id.to_string(),
+            inst_id: inst_id.to_string(),
+        }
+    }
+}
+
+/// 修改订单请求，ordId和clOrdId必须传一个，newSz和newPx至少传一个
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct AmendOrderRequest {
+    /// 产品ID，如 BTC-USDT
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    /// 当订单修改失败时，该订单是否需要自动撤销，默认为false
+    #[serde(rename = "cxlOnFail", skip_serializing_if = "Option::is_none")]
+    pub cxl_on_fail: Option<bool>,
+    #[serde(rename = "ordId", skip_serializing_if = "Option::is_none")]
+    pub ord_id: Option<String>,
+    /// 客户自定义订单ID
+    #[serde(rename = "clOrdId", skip_serializing_if = "Option::is_none")]
+    pub cl_ord_id: Option<String>,
+    /// 用户自定义修改事件ID
+    /// 字母（区分大小写）与数字的组合，可以是纯字母、纯数字且长度要在1-32位之间。
+    #[serde(rename = "reqId", skip_serializing_if = "Option::is_none")]
+    pub req_id: Option<String>,
+    /// 修改的新数量，对于部分成交订单，该数量应包含已成交数量
+    #[serde(rename = "newSz", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub new_sz: Option<Decimal>,
+    /// 修改后的新价格
+    #[serde(rename = "newPx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub new_px: Option<Decimal>,
+}
+
+impl AmendOrderRequest {
+    pub fn validate(&self) -> Result<(), OkxError> {
+        let invalid = |msg: &str| Err(OkxError::InvalidOrder(format!("{} {}", self.inst_id, msg)));
+
+        if self.ord_id.is_none() && self.cl_ord_id.is_none() {
+            return invalid("ord_id or cl_ord_id is required");
+        }
+        if self.new_sz.is_none() && self.new_px.is_none() {
+            return invalid("new_sz or new_px is required");
+        }
+        Ok(())
+    }
+}
+
+/// 市价仓位全平请求
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ClosePositionRequest {
+    /// 产品ID，如 BTC-USDT-SWAP
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    /// 持仓方向
+    /// 买卖模式下：可不填写此参数，默认值net，如果填写，仅可以填写net
+    /// 开平仓模式下：必须填写此参数，且仅可以填写 long：平多 ，short：平空
+    #[serde(rename = "posSide", skip_serializing_if = "Option::is_none")]
+    pub pos_side: Option<PositionSide>,
+    /// 保证金模式 cross：全仓 ； isolated：逐仓
+    #[serde(rename = "mgnMode")]
+    pub mgn_mode: TradeMode,
+    /// 保证金币种，仅适用于单币种保证金模式下的全仓杠杆仓位
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ccy: Option<String>,
+    /// 当市价全平时，平仓单是否需要自动撤销，默认为false
+    /// false：不自动撤单 true：自动撤单
+    #[serde(rename = "autoCxl", skip_serializing_if = "Option::is_none")]
+    pub auto_cxl: Option<bool>,
+    /// 客户自定义ID
+    #[serde(rename = "clOrdId", skip_serializing_if = "Option::is_none")]
+    pub cl_ord_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+/// 产品类型
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub enum InstType {
+    /// 币币
+    #[serde(rename="SPOT")]
+    Spot,
+    /// 币币杠杆
+    #[serde(rename="MARGIN")]
+    Margin,
+    /// 永续合约
+    #[serde(rename="SWAP")]
+    Swap,
+    /// 交割合约
+    #[serde(rename="FUTURES")]
+    Futures,
+    /// 期权
+    #[serde(rename="OPTION")]
+    Option,
+    /// 所有
+    #[serde(rename="ANY")]
+    Any,
+}
+
+// 获取订单信息
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BalanceDetailItem {
+    /// 币种
+    pub ccy: String,
+    /// 币种总权益
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub eq: Decimal,
+    /// 币种余额
+    #[serde(rename = "cashBal", serialize_with="to_str",deserialize_with="from_str")]
+    pub cash_bal: Decimal,
+    #[serde(rename = "uTime", serialize_with="to_str",deserialize_with="from_str")]
+    pub u_time: i64,
+    /// 币种逐仓仓位权益
+    /// 适用于单币种保证金模式和跨币种保证金模式和组合保证金模式
+    #[serde(rename = "isoEq", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub iso_eq: Option<Decimal>,
+    /// 可用保证金
+    /// 适用于单币种保证金模式和跨币种保证金模式和组合保证金模式
+    #[serde(rename = "availEq", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub avail_eq: Option<Decimal>,
+    /// 美金层面币种折算权益
+    #[serde(rename = "disEq", serialize_with="to_str",deserialize_with="from_str")]
+    pub dis_eq: Decimal,
+    /// 可用余额
+    /// 适用于简单交易模式、单币种保证金模式、跨币种保证金模式和组合保证金模式
+    #[serde(rename = "availBal", serialize_with="to_str",deserialize_with="from_str")]
+    pub avail_bal: Decimal,
+    /// 币种占用金额
+    #[serde(rename = "frozenBal", serialize_with="to_str",deserialize_with="from_str")]
+    pub frozen_bal: Decimal,
+    /// 挂单冻结数量
+    #[serde(rename = "ordFrozen", serialize_with="to_str",deserialize_with="from_str")]
+    pub ord_frozen: Decimal,
+    /// 币种负债额
+    /// 适用于跨币种保证金模式和组合保证金模式
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub liab: Option<Decimal>,
+    /// 未实现盈亏
+    /// 适用于单币种保证金模式和跨币种保证金模式和组合保证金模式
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub upl: Option<Decimal>,
+    /// 由于仓位未实现亏损导致的负债
+    /// 适用于跨币种保证金模式和组合保证金模式
+    #[serde(rename = "uplLiab", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub upl_liab: Option<Decimal>,
+    /// 币种全仓负债额
+    /// 适用于跨币种保证金模式和组合保证金模式
+    #[serde(rename = "crossLiab", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub cross_liab: Option<Decimal>,
+    /// 币种逐仓负债额
     /// 适用于跨币种保证金模式和组合保证金模式
-    pub interest: String,
+    #[serde(rename = "isoLiab", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub iso_liab: Option<Decimal>,
+    /// 保证金率
+    /// 适用于单币种保证金模式
+    #[serde(rename = "mgnRatio", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub mgn_ratio: Option<Decimal>,
+    #[serde(rename = "eqUsd", serialize_with="to_str",deserialize_with="from_str")]
+    pub eq_usd: Decimal,
+    /// 计息，应扣未扣利息。
+    /// 适用于跨币种保证金模式和组合保证金模式
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub interest: Option<Decimal>,
     /// 当前负债币种触发系统自动换币的风险
     /// 0、1、2、3、4、5其中之一，数字越大代表您的负债币种触发自动换币概率越高
     /// 适用于跨币种保证金模式和组合保证金模式
@@ -757,57 +1512,59 @@ pub struct BalanceDetailItem {
     pub twap: i32,
     /// 币种最大可借
     /// 适用于跨币种保证金模式和组合保证金模式 的全仓
-    #[serde(rename = "maxLoan")]
-    pub max_loan: String,
+    #[serde(rename = "maxLoan", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub max_loan: Option<Decimal>,
     /// 币种杠杆倍数
     /// 适用于单币种保证金模式
-    #[serde(rename = "notionalLever")]
-    pub notional_lever: String,
+    #[serde(rename = "notionalLever", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub notional_lever: Option<Decimal>,
     /// 币种权益美金价值
-    #[serde(rename = "stgyEq")]
-    pub stgy_eq: String,
+    #[serde(rename = "stgyEq", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub stgy_eq: Option<Decimal>,
     /// 逐仓未实现盈亏
     /// 适用于单币种保证金模式和跨币种保证金模式和组合保证金模式
-    #[serde(rename = "isoUpl")]
-    pub iso_upl: String,
+    #[serde(rename = "isoUpl", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub iso_upl: Option<Decimal>,
     /// 现货对冲占用数量
     /// 适用于组合保证金模式
-    #[serde(rename = "spotInUseAmt")]
-    pub spot_in_use_amt: String,
+    #[serde(rename = "spotInUseAmt", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub spot_in_use_amt: Option<Decimal>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AccountBalance {
     /// 美金层面有效保证金
     /// 适用于跨币种保证金模式和组合保证金模式
-    #[serde(rename = "adjEq")]
-    pub adj_eq: String,
+    #[serde(rename = "adjEq", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub adj_eq: Option<Decimal>,
     pub details: Vec<BalanceDetailItem>,
     /// 美金层面占用保证金
     /// 适用于跨币种保证金模式和组合保证金模式
-    pub imr: String,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub imr: Option<Decimal>,
     /// 美金层面逐仓仓位权益
     /// 适用于单币种保证金模式和跨币种保证金模式和组合保证金模式
-    #[serde(rename = "isoEq")]
-    pub iso_eq: String,
+    #[serde(rename = "isoEq", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub iso_eq: Option<Decimal>,
     /// 美金层面保证金率
     /// 适用于跨币种保证金模式 和组合保证金模式
-    #[serde(rename = "mgnRatio")]
-    pub mgn_ratio: String,
+    #[serde(rename = "mgnRatio", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub mgn_ratio: Option<Decimal>,
     /// 美金层面维持保证金
     /// 适用于跨币种保证金模式和组合保证金模式
-    pub mmr: String,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub mmr: Option<Decimal>,
     /// 以美金价值为单位的持仓数量，即仓位美金价值
     /// 适用于跨币种保证金模式和组合保证金模式
-    #[serde(rename = "notionalUsd")]
-    pub notional_usd: String,
+    #[serde(rename = "notionalUsd", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub notional_usd: Option<Decimal>,
     /// 金层面全仓挂单占用保证金
     /// 仅适用于跨币种保证金模式
-    #[serde(rename = "ordFroz")]
-    pub ord_froz: String,
+    #[serde(rename = "ordFroz", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub ord_froz: Option<Decimal>,
     /// 美金层面权益
-    #[serde(rename = "totalEq")]
-    pub total_eq: String,
+    #[serde(rename = "totalEq", serialize_with="to_str",deserialize_with="from_str")]
+    pub total_eq: Decimal,
     /// 账户信息的更新时间，Unix时间戳的毫秒数格式，如 1597026383085
     #[serde(rename = "uTime", serialize_with="to_str",deserialize_with="from_str")]
     pub u_time: i64,
@@ -832,9 +1589,11 @@ pub struct Trade {
     #[serde(rename = "tradeId", serialize_with="to_str",deserialize_with="from_str")]
     pub trade_id: i64,
     /// 成交价格
-    pub px: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub px: Decimal,
     /// 成交数量
-    pub sz: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub sz: Decimal,
     /// 成交方向，buy sell
     pub side: String,
     /// 成交时间，Unix时间戳的毫秒数格式，如 1597026383085
@@ -842,86 +1601,637 @@ pub struct Trade {
     pub ts: i64,
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
-pub enum GreeksType {
-    /// 币本位
-    #[serde(rename="PA")]
-    Pa,
-    /// 美元本位
-    #[serde(rename="BS")]
-    Bs
+/// 成交明细
+/// GET /api/v5/trade/fills
+/// GET /api/v5/trade/fills-history
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fill {
+    pub inst_type: InstType,
+    pub inst_id: String,
+    /// 最新成交ID
+    pub trade_id: String,
+    pub ord_id: String,
+    #[serde(default)]
+    pub cl_ord_id: String,
+    /// 账单ID，分页时作为after、before的参数
+    pub bill_id: String,
+    #[serde(default)]
+    pub tag: String,
+    /// 最新成交价格
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub fill_px: Decimal,
+    /// 最新成交数量
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub fill_sz: Decimal,
+    /// 交易执行时的指数价格
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub fill_idx_px: Option<Decimal>,
+    /// 最新成交收益，适用于有成交的平仓订单，其他情况均为0
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub fill_pnl: Option<Decimal>,
+    /// 交易执行时的标记价格，仅适用于 交割/永续/期权
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub fill_mark_px: Option<Decimal>,
+    pub side: TradeSide,
+    /// 持仓方向 long short，买卖模式返回 net
+    pub pos_side: PositionSide,
+    /// 流动性方向 T：taker M：maker
+    pub exec_type: ExecuteType,
+    /// 交易手续费币种或者返佣金币种
+    pub fee_ccy: String,
+    /// 手续费金额或者返佣金额，手续费扣除为负数，如-0.01；手续费返佣为正数，如 0.01
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub fee: Decimal,
+    /// 成交明细产生时间，Unix时间戳的毫秒数格式
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub ts: i64,
+    /// 成交时间，与订单频道的fillTime相同
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub fill_time: Option<i64>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct AccountConfig {
-    /// 当前请求的账户ID，账户uid和app上的一致
-    pub uid: String,
-    /// 当前请求的母账户ID
-    /// 如果 uid = mainUid，代表当前账号为母账户；如果 uid != mainUid，代表当前账户为子账户。
-    #[serde(rename = "mainUid")]
-    pub main_uid: String,
-    /// 账户层级
-    /// 1：简单交易模式，2：单币种保证金模式 ，3：跨币种保证金模式 ，4：组合保证金模式
-    #[serde(rename = "acctLv", serialize_with="to_str",deserialize_with="from_str")]
-    pub acct_lv: u32,
-    /// 持仓方式
-    /// long_short_mode：开平仓模式 net_mode：买卖模式
-    /// 仅适用交割/永续
-    #[serde(rename = "posMode")]
-    pub pos_mode: String,
-    /// 是否自动借币
-    /// true：自动借币 false：非自动借币
-    #[serde(rename = "autoLoan")]
-    pub auto_loan: bool,
-    /// 当前希腊字母展示方式
-    /// PA：币本位 BS：美元本位
-    #[serde(rename = "greeksType")]
-    pub greeks_type: GreeksType,
-    /// 当前在平台上真实交易量的用户等级，例如 lv1
-    // #[serde(serialize_with="to_str",deserialize_with="from_str")]
-    pub level: Level,
-    /// 特约用户的临时体验用户等级，例如 lv3
-    #[serde(rename = "levelTmp")]
-    pub level_tmp: Level,
-    /// 衍生品的逐仓保证金划转模式
-    /// automatic：开仓划转 autonomy：自主划转
-    #[serde(rename = "ctIsoMode")]
-    pub ct_iso_mode: String,
-    /// 币币杠杆的逐仓保证金划转模式
-    /// automatic：开仓划转 quick_margin：一键借币（对于新的账户，包括新的子账户，有些默认是开仓划转，另外的默认是一键借币）
-    #[serde(rename = "mgnIsoMode")]
-    pub mgn_iso_mode: String,
-    /// 现货对冲类型
-    /// 1：现货对冲模式U模式 2：现货对冲模式币模式 3：非现货对冲模式
-    /// 适用于组合保证金模式
-    #[serde(rename = "spotOffsetType")]
-    pub spot_offset_type: String,
-    /// 用户角色。
-    /// 0：普通用户；1：带单者；2：跟单者
-    #[serde(rename = "roleType")]
-    pub role_type: String,
-    /// 当前账号已经设置的带单合约，仅适用于带单者
-    #[serde(rename = "traderInsts")]
-    pub trader_insts: Vec<String>,
-    /// 是否开通期权交易
-    /// 0 未开通，1 已经开通
-    #[serde(rename = "opAuth")]
-    pub op_auth: String,
-    /// 母账户KYC等级
-    /// 0: 未认证 1: 已完成 level 1 认证, 2: 已完成 level 2 认证, 3: 已完成 level 3认证.
-    /// 如果请求来自子账户, kycLv 为其母账户的等级.
-    /// 如果请求来自母账户, kycLv 为当前请求的母账户等级.
-    #[serde(rename = "kycLv")]
-    pub kyc_lv: String,
-    /// 当前请求API key的备注名，不超过50位字母（区分大小写）或数字，可以是纯字母或纯数字。
-    pub label: String,
-    /// 当前请求API key绑定的ip地址，多个ip用半角逗号隔开，如：117.37.203.58,117.37.203.57。
-    /// 如果没有绑定ip，会返回空字符串""
-    pub ip: String,
-    /// 当前请求的 API key权限 read_only：只读；trade ：交易；withdraw: 提币
-    pub perm: String,
-}
-impl AccountConfig {
+/// 永续合约当前资金费率
+/// GET /api/v5/public/funding-rate
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingRate {
+    pub inst_type: InstType,
+    pub inst_id: String,
+    /// 资金费收取逻辑 current_period：当期收 next_period：跨期收
+    pub method: String,
+    /// 资金费率
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub funding_rate: Decimal,
+    /// 下一期预测资金费率，method为current_period时为空
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub next_funding_rate: Option<Decimal>,
+    /// 资金费时间，Unix时间戳的毫秒数格式
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub funding_time: i64,
+    /// 下一期资金费时间
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub next_funding_time: i64,
+    /// 资金费率下限
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub min_funding_rate: Option<Decimal>,
+    /// 资金费率上限
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub max_funding_rate: Option<Decimal>,
+    /// 资金费率结算状态 processing：结算中 settled：已结算
+    #[serde(default)]
+    pub sett_state: String,
+    /// 若sett_state为processing，该字段代表用于本轮结算的资金费率
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub sett_funding_rate: Option<Decimal>,
+    /// 溢价指数
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub premium: Option<Decimal>,
+    /// 数据更新时间
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub ts: i64,
+}
+
+/// 永续合约历史资金费率
+/// GET /api/v5/public/funding-rate-history
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingRateHistory {
+    pub inst_type: InstType,
+    pub inst_id: String,
+    /// 预计资金费率
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub funding_rate: Decimal,
+    /// 实际资金费率
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub realized_rate: Decimal,
+    /// 资金费时间
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub funding_time: i64,
+    /// 资金费收取逻辑 current_period：当期收 next_period：跨期收
+    pub method: String,
+}
+
+/// 单个交易产品的市场的持仓总量
+/// GET /api/v5/public/open-interest
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenInterest {
+    pub inst_type: InstType,
+    pub inst_id: String,
+    /// 持仓量，按张为单位
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub oi: Decimal,
+    /// 持仓量，按币为单位
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub oi_ccy: Decimal,
+    /// 持仓量，按美元为单位
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub oi_usd: Option<Decimal>,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub ts: i64,
+}
+
+/// 标记价格
+/// GET /api/v5/public/mark-price
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkPrice {
+    pub inst_type: InstType,
+    pub inst_id: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub mark_px: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub ts: i64,
+}
+
+/// 交易产品的最高买价和最低卖价
+/// GET /api/v5/public/price-limit
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceLimit {
+    pub inst_type: InstType,
+    pub inst_id: String,
+    /// 最高买价，enabled为false时为空
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub buy_lmt: Option<Decimal>,
+    /// 最低卖价，enabled为false时为空
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub sell_lmt: Option<Decimal>,
+    /// 限价是否生效
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub ts: i64,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 交割合约和期权预估交割/行权价格，只有交割/行权预估结算前一小时才有返回值
+/// GET /api/v5/public/estimated-price
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimatedPrice {
+    pub inst_type: InstType,
+    pub inst_id: String,
+    /// 预估交割、行权价格
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub settle_px: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub ts: i64,
+}
+
+/// 衍生品仓位档位
+/// GET /api/v5/public/position-tiers
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionTier {
+    pub uly: String,
+    pub inst_family: String,
+    /// 币对，仅适用于币币杠杆
+    #[serde(default)]
+    pub inst_id: String,
+    /// 仓位档位
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub tier: u32,
+    /// 该档位最少持仓数量，期权/永续/交割最小持仓量 默认0
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub min_sz: Decimal,
+    /// 该档位最多持仓数量
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub max_sz: Decimal,
+    /// 维持保证金率
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub mmr: Decimal,
+    /// 初始保证金率
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub imr: Decimal,
+    /// 最高可用杠杆倍数
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub max_lever: Decimal,
+    /// 期权保证金系数，仅适用于期权
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub opt_mgn_factor: Option<Decimal>,
+    /// 计价货币最大借币量，仅适用于币币杠杆
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub quote_max_loan: Option<Decimal>,
+    /// 交易货币最大借币量，仅适用于币币杠杆
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub base_max_loan: Option<Decimal>,
+}
+
+/// 风险准备金余额
+/// GET /api/v5/public/insurance-fund
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsuranceFund {
+    /// 平台风险准备金总计，单位为美元
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub total: Decimal,
+    #[serde(default)]
+    pub inst_family: String,
+    pub inst_type: InstType,
+    pub details: Vec<InsuranceFundDetail>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsuranceFundDetail {
+    /// 风险准备金余额
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub balance: Decimal,
+    /// 风险准备金余额变动数量
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub amt: Option<Decimal>,
+    pub ccy: String,
+    /// 风险准备金类型 regular_update liquidation_balance_deposit bankruptcy_loss platform_revenue adl
+    #[serde(rename = "type")]
+    pub fund_type: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub ts: i64,
+}
+
+/// 系统时间
+/// GET /api/v5/public/time
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PublicTime {
+    /// 系统时间，Unix时间戳的毫秒数格式
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub ts: i64,
+}
+
+/// 系统维护状态
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SystemState {
+    /// 等待中
+    #[serde(rename="scheduled")]
+    Scheduled,
+    /// 进行中
+    #[serde(rename="ongoing")]
+    Ongoing,
+    /// 预开放
+    #[serde(rename="pre_open")]
+    PreOpen,
+    /// 已完成
+    #[serde(rename="completed")]
+    Completed,
+    /// 已取消
+    #[serde(rename="canceled")]
+    Canceled,
+}
+
+/// 系统升级事件
+/// GET /api/v5/system/status
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemStatus {
+    pub title: String,
+    pub state: SystemState,
+    /// 系统维护的开始时间，Unix时间戳的毫秒数格式
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub begin: i64,
+    /// 系统维护的结束时间，维护完成前为预期结束时间
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub end: i64,
+    /// 预开放开始的时间，开放撤单、Post Only 下单和资金转入功能的时间
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub pre_open_begin: Option<i64>,
+    /// 系统维护详情的超级链接
+    #[serde(default)]
+    pub href: String,
+    /// 服务类型 0:WebSocket 5:交易服务 6:大宗交易 7:策略交易 8:交易服务(按账户分批次) 9:交易服务(按产品分批次)
+    /// 10:跟单交易 99:其他
+    #[serde(default)]
+    pub service_type: String,
+    /// 系统 unified:交易账户
+    #[serde(default)]
+    pub system: String,
+    /// 改期进度说明
+    #[serde(default)]
+    pub sche_desc: String,
+    /// 维护类型 1:计划维护 2:临时维护 3:系统故障
+    #[serde(default)]
+    pub maint_type: String,
+    /// 环境 1:实盘 2:模拟盘
+    #[serde(default)]
+    pub env: String,
+}
+
+/// K线周期，默认为香港时间开盘价，带utc的为UTC时间开盘价
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub enum Bar {
+    #[serde(rename="1m")]
+    Min1,
+    #[serde(rename="3m")]
+    Min3,
+    #[serde(rename="5m")]
+    Min5,
+    #[serde(rename="15m")]
+    Min15,
+    #[serde(rename="30m")]
+    Min30,
+    #[serde(rename="1H")]
+    Hour1,
+    #[serde(rename="2H")]
+    Hour2,
+    #[serde(rename="4H")]
+    Hour4,
+    #[serde(rename="6H")]
+    Hour6,
+    #[serde(rename="12H")]
+    Hour12,
+    #[serde(rename="1D")]
+    Day1,
+    #[serde(rename="2D")]
+    Day2,
+    #[serde(rename="3D")]
+    Day3,
+    #[serde(rename="1W")]
+    Week1,
+    #[serde(rename="1M")]
+    Month1,
+    #[serde(rename="3M")]
+    Month3,
+    #[serde(rename="6Hutc")]
+    Hour6Utc,
+    #[serde(rename="12Hutc")]
+    Hour12Utc,
+    #[serde(rename="1Dutc")]
+    Day1Utc,
+    #[serde(rename="2Dutc")]
+    Day2Utc,
+    #[serde(rename="3Dutc")]
+    Day3Utc,
+    #[serde(rename="1Wutc")]
+    Week1Utc,
+    #[serde(rename="1Mutc")]
+    Month1Utc,
+    #[serde(rename="3Mutc")]
+    Month3Utc,
+}
+
+/// K线数据的来源
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub enum CandleType {
+    /// 最近的K线，GET /api/v5/market/candles
+    Candles,
+    /// 历史K线，GET /api/v5/market/history-candles
+    HistoryCandles,
+    /// 指数K线，GET /api/v5/market/index-candles
+    IndexCandles,
+    /// 标记价格K线，GET /api/v5/market/mark-price-candles
+    MarkPriceCandles,
+}
+
+/// K线数据，okx按数组返回：[ts,o,h,l,c,vol,volCcy,volCcyQuote,confirm]
+/// 指数和标记价格K线没有成交量：[ts,o,h,l,c,confirm]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<String>", into = "Vec<String>")]
+pub struct Candle {
+    /// 开始时间，Unix时间戳的毫秒数格式
+    pub ts: i64,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    /// 交易量，合约的单位是张，币币的单位是交易货币
+    pub vol: Option<Decimal>,
+    /// 交易量，以币为单位
+    pub vol_ccy: Option<Decimal>,
+    /// 交易量，以计价货币为单位
+    pub vol_ccy_quote: Option<Decimal>,
+    /// K线是否完结，false代表K线未完结，true代表K线已完结
+    pub confirm: bool,
+}
+
+impl TryFrom<Vec<String>> for Candle {
+    type Error = OkxError;
+
+    fn try_from(value: Vec<String>) -> Result<Self, Self::Error> {
+        let decimal = |val: &String| val.parse::<Decimal>();
+        let opt_decimal = |val: Option<&String>| match val {
+            Some(val) if !val.is_empty() => val.parse::<Decimal>().map(Some),
+            _ => Ok(None),
+        };
+
+        let (volumes, confirm) = match value.len() {
+            6 => (&value[5..5], &value[5]),
+            9 => (&value[5..8], &value[8]),
+            _ => return Err(OkxError::SerdeError(serde::de::Error::custom(format!("invalid candle: {:?}", value)))),
+        };
+        Ok(Self {
+            ts: value[0].parse()?,
+            open: decimal(&value[1])?,
+            high: decimal(&value[2])?,
+            low: decimal(&value[3])?,
+            close: decimal(&value[4])?,
+            vol: opt_decimal(volumes.first())?,
+            vol_ccy: opt_decimal(volumes.get(1))?,
+            vol_ccy_quote: opt_decimal(volumes.get(2))?,
+            confirm: confirm == "1",
+        })
+    }
+}
+
+impl From<Candle> for Vec<String> {
+    fn from(value: Candle) -> Self {
+        let mut result = vec![
+            value.ts.to_string(),
+            value.open.to_string(),
+            value.high.to_string(),
+            value.low.to_string(),
+            value.close.to_string(),
+        ];
+        if let Some(vol) = value.vol {
+            result.push(vol.to_string());
+            result.push(value.vol_ccy.map(|val| val.to_string()).unwrap_or_default());
+            result.push(value.vol_ccy_quote.map(|val| val.to_string()).unwrap_or_default());
+        }
+        result.push(if value.confirm { "1" } else { "0" }.to_string());
+        result
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub enum GreeksType {
+    /// 币本位
+    #[serde(rename="PA")]
+    Pa,
+    /// 美元本位
+    #[serde(rename="BS")]
+    Bs
+}
+
+/// 持仓方式，仅适用交割/永续
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub enum PositionMode {
+    /// 开平仓模式
+    #[serde(rename="long_short_mode")]
+    LongShortMode,
+    /// 买卖模式
+    #[serde(rename="net_mode")]
+    NetMode,
+}
+
+/// 逐仓保证金划转模式
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub enum IsoMode {
+    /// 开仓划转
+    #[serde(rename="automatic")]
+    Automatic,
+    /// 自主划转
+    #[serde(rename="autonomy")]
+    Autonomy,
+    /// 一键借币，仅适用于币币杠杆
+    #[serde(rename="quick_margin")]
+    QuickMargin,
+}
+
+/// 设置逐仓模式时的业务线类型
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub enum IsoModeType {
+    /// 币币杠杆
+    #[serde(rename="MARGIN")]
+    Margin,
+    /// 合约
+    #[serde(rename="CONTRACTS")]
+    Contracts,
+}
+
+/// 逐仓保证金调整方向
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub enum MarginBalanceType {
+    /// 增加
+    #[serde(rename="add")]
+    Add,
+    /// 减少
+    #[serde(rename="reduce")]
+    Reduce,
+}
+
+/// 设置持仓模式
+/// POST /api/v5/account/set-position-mode
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetPositionMode {
+    pub pos_mode: PositionMode,
+}
+
+/// 设置希腊字母的展示方式
+/// POST /api/v5/account/set-greeks
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetGreeks {
+    pub greeks_type: GreeksType,
+}
+
+/// 逐仓交易设置
+/// POST /api/v5/account/set-isolated-mode
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetIsolatedMode {
+    pub iso_mode: IsoMode,
+}
+
+/// 调整保证金
+/// POST /api/v5/account/position/margin-balance
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginBalance {
+    pub inst_id: String,
+    pub pos_side: PositionSide,
+    /// 已增加/减少的保证金数量
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub amt: Decimal,
+    #[serde(rename = "type")]
+    pub balance_type: MarginBalanceType,
+    /// 调整保证金后的实际杠杆倍数
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub leverage: Option<Decimal>,
+    #[serde(default)]
+    pub ccy: String,
+}
+
+/// 设置自动借币
+/// POST /api/v5/account/set-auto-loan
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetAutoLoan {
+    pub auto_loan: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AccountConfig {
+    /// 当前请求的账户ID，账户uid和app上的一致
+    pub uid: String,
+    /// 当前请求的母账户ID
+    /// 如果 uid = mainUid，代表当前账号为母账户；如果 uid != mainUid，代表当前账户为子账户。
+    #[serde(rename = "mainUid")]
+    pub main_uid: String,
+    /// 账户层级
+    /// 1：简单交易模式，2：单币种保证金模式 ，3：跨币种保证金模式 ，4：组合保证金模式
+    #[serde(rename = "acctLv", serialize_with="to_str",deserialize_with="from_str")]
+    pub acct_lv: u32,
+    /// 持仓方式
+    /// long_short_mode：开平仓模式 net_mode：买卖模式
+    /// 仅适用交割/永续
+    #[serde(rename = "posMode")]
+    pub pos_mode: PositionMode,
+    /// 是否自动借币
+    /// true：自动借币 false：非自动借币
+    #[serde(rename = "autoLoan")]
+    pub auto_loan: bool,
+    /// 当前希腊字母展示方式
+    /// PA：币本位 BS：美元本位
+    #[serde(rename = "greeksType")]
+    pub greeks_type: GreeksType,
+    /// 当前在平台上真实交易量的用户等级，例如 lv1
+    // #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub level: Level,
+    /// 特约用户的临时体验用户等级，例如 lv3
+    #[serde(rename = "levelTmp")]
+    pub level_tmp: Level,
+    /// 衍生品的逐仓保证金划转模式
+    /// automatic：开仓划转 autonomy：自主划转
+    #[serde(rename = "ctIsoMode")]
+    pub ct_iso_mode: IsoMode,
+    /// 币币杠杆的逐仓保证金划转模式
+    /// automatic：开仓划转 quick_margin：一键借币（对于新的账户，包括新的子账户，有些默认是开仓划转，另外的默认是一键借币）
+    #[serde(rename = "mgnIsoMode")]
+    pub mgn_iso_mode: IsoMode,
+    /// 现货对冲类型
+    /// 1：现货对冲模式U模式 2：现货对冲模式币模式 3：非现货对冲模式
+    /// 适用于组合保证金模式
+    #[serde(rename = "spotOffsetType")]
+    pub spot_offset_type: String,
+    /// 用户角色。
+    /// 0：普通用户；1：带单者；2：跟单者
+    #[serde(rename = "roleType")]
+    pub role_type: String,
+    /// 当前账号已经设置的带单合约，仅适用于带单者
+    #[serde(rename = "traderInsts")]
+    pub trader_insts: Vec<String>,
+    /// 是否开通期权交易
+    /// 0 未开通，1 已经开通
+    #[serde(rename = "opAuth")]
+    pub op_auth: String,
+    /// 母账户KYC等级
+    /// 0: 未认证 1: 已完成 level 1 认证, 2: 已完成 level 2 认证, 3: 已完成 level 3认证.
+    /// 如果请求来自子账户, kycLv 为其母账户的等级.
+    /// 如果请求来自母账户, kycLv 为当前请求的母账户等级.
+    #[serde(rename = "kycLv")]
+    pub kyc_lv: String,
+    /// 当前请求API key的备注名，不超过50位字母（区分大小写）或数字，可以是纯字母或纯数字。
+    pub label: String,
+    /// 当前请求API key绑定的ip地址，多个ip用半角逗号隔开，如：117.37.203.58,117.37.203.57。
+    /// 如果没有绑定ip，会返回空字符串""
+    pub ip: String,
+    /// 当前请求的 API key权限 read_only：只读；trade ：交易；withdraw: 提币
+    pub perm: String,
+}
+impl AccountConfig {
     pub fn permission(&self) -> Vec<ApiKeyPermission>{
         let mut result = vec![];
         for item in self.perm.split(",") {
@@ -972,29 +2282,1265 @@ pub enum ApiKeyPermission{
     Withdraw
 }
 
-macro_rules! impl_to_str {
-    ($($arg:tt)*) => {
-        $(
-        impl Into<String> for $arg {
-            fn into(self) -> String {
-                let str = serde_json::to_string(&self).unwrap();
-                serde_json::from_str(&str).unwrap()
-            }
-        }
-        )*
-    };
+/// 策略委托类型
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub enum AlgoOrderType {
+    /// 单向止盈止损
+    #[serde(rename="conditional")]
+    Conditional,
+    /// 双向止盈止损
+    #[serde(rename="oco")]
+    Oco,
+    /// 计划委托
+    #[serde(rename="trigger")]
+    Trigger,
+    /// 移动止盈止损
+    #[serde(rename="move_order_stop")]
+    MoveOrderStop,
+    /// 冰山委托
+    #[serde(rename="iceberg")]
+    Iceberg,
+    /// 时间加权委托
+    #[serde(rename="twap")]
+    Twap,
 }
 
-impl_to_str!(
-    TradeMode
-    TradeSide
-    PositionSide
-    OrderType
-    StopMode
-    TpTriggerPxType
+/// 策略委托订单状态
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub enum AlgoOrderState {
+    /// 待生效
+    #[serde(rename="live")]
+    Live,
+    /// 暂停生效
+    #[serde(rename="pause")]
+    Pause,
+    /// 部分生效
+    #[serde(rename="partially_effective")]
+    PartiallyEffective,
+    /// 已生效
+    #[serde(rename="effective")]
+    Effective,
+    /// 已撤销
+    #[serde(rename="canceled")]
+    Canceled,
+    /// 委托失败
+    #[serde(rename="order_failed")]
+    OrderFailed,
+    /// 部分委托失败
+    #[serde(rename="partially_failed")]
+    PartiallyFailed,
+}
+
+/// 策略委托的公共参数
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct AlgoOrderBase {
+    /// 产品ID，如 BTC-USDT
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    /// 交易模式 isolated：逐仓 cross：全仓 cash：非保证金
+    #[serde(rename = "tdMode")]
+    pub td_mode: TradeMode,
+    /// 保证金币种，仅适用于单币种保证金模式下的全仓杠杆订单
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ccy: Option<String>,
+    pub side: TradeSide,
+    /// 持仓方向，在开平仓模式下必填，且仅可选择 long 或 short
+    #[serde(rename="posSide", skip_serializing_if = "Option::is_none")]
+    pub pos_side: Option<PositionSide>,
+    /// 委托数量
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub sz: Decimal,
+    /// 订单标签
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// 委托数量的类型，仅适用于币币订单 base_ccy: 交易货币 quote_ccy：计价货币
+    #[serde(rename = "tgtCcy", skip_serializing_if = "Option::is_none")]
+    pub tgt_ccy: Option<String>,
+    /// 客户自定义策略订单ID
+    #[serde(rename = "algoClOrdId", skip_serializing_if = "Option::is_none")]
+    pub algo_cl_ord_id: Option<String>,
+    /// 是否只减仓
+    #[serde(rename = "reduceOnly", skip_serializing_if = "Option::is_none")]
+    pub reduce_only: Option<bool>,
+}
+
+/// 止盈止损参数，单向止盈止损和双向止盈止损使用
+/// 委托价格为-1时，执行市价止盈止损
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct AlgoTpSl {
+    #[serde(flatten)]
+    pub base: AlgoOrderBase,
+    #[serde(rename="tpTriggerPx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub tp_trigger_px: Option<Decimal>,
+    #[serde(rename="tpTriggerPxType", skip_serializing_if = "Option::is_none")]
+    pub tp_trigger_px_type: Option<TpTriggerPxType>,
+    #[serde(rename="tpOrdPx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub tp_ord_px: Option<Decimal>,
+    #[serde(rename="slTriggerPx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub sl_trigger_px: Option<Decimal>,
+    #[serde(rename="slTriggerPxType", skip_serializing_if = "Option::is_none")]
+    pub sl_trigger_px_type: Option<TpTriggerPxType>,
+    #[serde(rename="slOrdPx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub sl_ord_px: Option<Decimal>,
+    /// 是否与仓位关联，仓位全平后自动撤单
+    #[serde(rename="cxlOnClosePos", skip_serializing_if = "Option::is_none")]
+    pub cxl_on_close_pos: Option<bool>,
+}
+
+/// 计划委托参数
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct AlgoTrigger {
+    #[serde(flatten)]
+    pub base: AlgoOrderBase,
+    /// 计划委托触发价格
+    #[serde(rename="triggerPx", serialize_with="to_str",deserialize_with="from_str")]
+    pub trigger_px: Decimal,
+    /// 委托价格，为-1时执行市价委托
+    #[serde(rename="orderPx", serialize_with="to_str",deserialize_with="from_str")]
+    pub order_px: Decimal,
+    #[serde(rename="triggerPxType", skip_serializing_if = "Option::is_none")]
+    pub trigger_px_type: Option<TpTriggerPxType>,
+}
+
+/// 移动止盈止损参数，callback_ratio和callback_spread只能传一个
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct AlgoMoveOrderStop {
+    #[serde(flatten)]
+    pub base: AlgoOrderBase,
+    /// 回调幅度的比例，如 0.05 代表 5%
+    #[serde(rename="callbackRatio", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub callback_ratio: Option<Decimal>,
+    /// 回调幅度的价距
+    #[serde(rename="callbackSpread", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub callback_spread: Option<Decimal>,
+    /// 激活价格，不填写时立即激活
+    #[serde(rename="activePx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub active_px: Option<Decimal>,
+}
+
+/// 冰山委托和时间加权委托参数，px_var和px_spread只能传一个
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct AlgoIceberg {
+    #[serde(flatten)]
+    pub base: AlgoOrderBase,
+    /// 距离盘口的比例，0.0001 ~ 0.01
+    #[serde(rename="pxVar", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub px_var: Option<Decimal>,
+    /// 距离盘口的价距
+    #[serde(rename="pxSpread", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub px_spread: Option<Decimal>,
+    /// 单笔数量
+    #[serde(rename="szLimit", serialize_with="to_str",deserialize_with="from_str")]
+    pub sz_limit: Decimal,
+    /// 挂单限制价
+    #[serde(rename="pxLimit", serialize_with="to_str",deserialize_with="from_str")]
+    pub px_limit: Decimal,
+    /// 下单间隔，单位为秒，仅适用于时间加权委托
+    #[serde(rename="timeInterval", skip_serializing_if = "Option::is_none")]
+    pub time_interval: Option<String>,
+}
+
+/// 策略委托下单请求，按ordType区分不同的策略参数
+/// POST /api/v5/trade/order-algo
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(tag = "ordType")]
+pub enum AlgoOrderRequest {
+    #[serde(rename="conditional")]
+    Conditional(AlgoTpSl),
+    #[serde(rename="oco")]
+    Oco(AlgoTpSl),
+    #[serde(rename="trigger")]
+    Trigger(AlgoTrigger),
+    #[serde(rename="move_order_stop")]
+    MoveOrderStop(AlgoMoveOrderStop),
+    #[serde(rename="iceberg")]
+    Iceberg(AlgoIceberg),
+    #[serde(rename="twap")]
+    Twap(AlgoIceberg),
+}
+
+impl AlgoOrderRequest {
+    pub fn base(&self) -> &AlgoOrderBase {
+        match self {
+            AlgoOrderRequest::Conditional(item) | AlgoOrderRequest::Oco(item) => &item.base,
+            AlgoOrderRequest::Trigger(item) => &item.base,
+            AlgoOrderRequest::MoveOrderStop(item) => &item.base,
+            AlgoOrderRequest::Iceberg(item) | AlgoOrderRequest::Twap(item) => &item.base,
+        }
+    }
+
+    pub fn ord_type(&self) -> AlgoOrderType {
+        match self {
+            AlgoOrderRequest::Conditional(_) => AlgoOrderType::Conditional,
+            AlgoOrderRequest::Oco(_) => AlgoOrderType::Oco,
+            AlgoOrderRequest::Trigger(_) => AlgoOrderType::Trigger,
+            AlgoOrderRequest::MoveOrderStop(_) => AlgoOrderType::MoveOrderStop,
+            AlgoOrderRequest::Iceberg(_) => AlgoOrderType::Iceberg,
+            AlgoOrderRequest::Twap(_) => AlgoOrderType::Twap,
+        }
+    }
+
+    /// 检查互斥的策略参数，不符合时返回InvalidOrder错误
+    pub fn validate(&self) -> Result<(), OkxError> {
+        let invalid = |msg: &str| Err(OkxError::InvalidOrder(format!("{} {}", self.base().inst_id, msg)));
+
+        match self {
+            AlgoOrderRequest::MoveOrderStop(item) if item.callback_ratio.is_some() == item.callback_spread.is_some() => {
+                invalid("exactly one of callback_ratio and callback_spread is required")
+            }
+            AlgoOrderRequest::Iceberg(item) | AlgoOrderRequest::Twap(item) if item.px_var.is_some() == item.px_spread.is_some() => {
+                invalid("exactly one of px_var and px_spread is required")
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 修改策略委托订单请求，仅支持止盈止损和计划委托，algoId和algoClOrdId必须传一个
+/// POST /api/v5/trade/amend-algos
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct AmendAlgoRequest {
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    #[serde(rename = "algoId", skip_serializing_if = "Option::is_none")]
+    pub algo_id: Option<String>,
+    #[serde(rename = "algoClOrdId", skip_serializing_if = "Option::is_none")]
+    pub algo_cl_ord_id: Option<String>,
+    /// 当订单修改失败时，该订单是否需要自动撤销，默认为false
+    #[serde(rename = "cxlOnFail", skip_serializing_if = "Option::is_none")]
+    pub cxl_on_fail: Option<bool>,
+    /// 用户自定义修改事件ID
+    #[serde(rename = "reqId", skip_serializing_if = "Option::is_none")]
+    pub req_id: Option<String>,
+    #[serde(rename = "newSz", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub new_sz: Option<Decimal>,
+    #[serde(rename = "newTpTriggerPx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub new_tp_trigger_px: Option<Decimal>,
+    #[serde(rename = "newTpOrdPx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub new_tp_ord_px: Option<Decimal>,
+    #[serde(rename = "newSlTriggerPx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub new_sl_trigger_px: Option<Decimal>,
+    #[serde(rename = "newSlOrdPx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub new_sl_ord_px: Option<Decimal>,
+    #[serde(rename = "newTpTriggerPxType", skip_serializing_if = "Option::is_none")]
+    pub new_tp_trigger_px_type: Option<TpTriggerPxType>,
+    #[serde(rename = "newSlTriggerPxType", skip_serializing_if = "Option::is_none")]
+    pub new_sl_trigger_px_type: Option<TpTriggerPxType>,
+    /// 计划委托修改后的触发价格
+    #[serde(rename = "newTriggerPx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub new_trigger_px: Option<Decimal>,
+    /// 计划委托修改后的委托价格
+    #[serde(rename = "newOrdPx", skip_serializing_if = "Option::is_none", default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub new_ord_px: Option<Decimal>,
+}
+
+impl AmendAlgoRequest {
+    pub fn validate(&self) -> Result<(), OkxError> {
+        if self.algo_id.is_none() && self.algo_cl_ord_id.is_none() {
+            return Err(OkxError::InvalidOrder(format!("{} algo_id or algo_cl_ord_id is required", self.inst_id)));
+        }
+        Ok(())
+    }
+}
+
+/// 策略委托下单结果
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeOrderAlgo {
+    pub algo_id: String,
+    #[serde(default)]
+    pub cl_ord_id: String,
+    #[serde(default)]
+    pub algo_cl_ord_id: String,
+    #[serde(default)]
+    pub tag: String,
+    pub s_code: String,
+    pub s_msg: String,
+}
+
+/// 撤销策略委托订单结果
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeCancelAlgos {
+    pub algo_id: String,
+    #[serde(default)]
+    pub cl_ord_id: String,
+    #[serde(default)]
+    pub algo_cl_ord_id: String,
+    pub s_code: String,
+    pub s_msg: String,
+}
+
+/// 修改策略委托订单结果
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeAmendAlgos {
+    pub algo_id: String,
+    #[serde(default)]
+    pub algo_cl_ord_id: String,
+    #[serde(default)]
+    pub req_id: String,
+    pub s_code: String,
+    pub s_msg: String,
+}
+
+/// 策略委托订单详情，获取策略委托单信息、未完成策略委托单列表、历史策略委托单列表使用相同的结构
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlgoOrder {
+    pub inst_type: InstType,
+    pub inst_id: String,
+    #[serde(default)]
+    pub ccy: String,
+    /// 最新一笔订单ID，即将废弃
+    #[serde(default)]
+    pub ord_id: String,
+    /// 订单ID列表，当止盈止损存在市价拆单时，会有多个
+    #[serde(default)]
+    pub ord_id_list: Vec<String>,
+    pub algo_id: String,
+    #[serde(default)]
+    pub cl_ord_id: String,
+    #[serde(default)]
+    pub algo_cl_ord_id: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub sz: Decimal,
+    /// 策略委托触发时，平仓的百分比，1 代表100%
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub close_fraction: Option<Decimal>,
+    pub ord_type: AlgoOrderType,
+    pub side: TradeSide,
+    pub pos_side: PositionSide,
+    pub td_mode: TradeMode,
+    #[serde(default)]
+    pub tgt_ccy: String,
+    pub state: AlgoOrderState,
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub lever: Option<Decimal>,
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub tp_trigger_px: Option<Decimal>,
+    pub tp_trigger_px_type: TpTriggerPxType,
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub tp_ord_px: Option<Decimal>,
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub sl_trigger_px: Option<Decimal>,
+    pub sl_trigger_px_type: TpTriggerPxType,
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub sl_ord_px: Option<Decimal>,
+    /// 计划委托的触发价格
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub trigger_px: Option<Decimal>,
+    pub trigger_px_type: TpTriggerPxType,
+    /// 计划委托的委托价格
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub ord_px: Option<Decimal>,
+    /// 实际委托量
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub actual_sz: Option<Decimal>,
+    /// 实际委托价
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub actual_px: Option<Decimal>,
+    /// 实际触发方向 tp：止盈 sl：止损，仅适用于单向止盈止损委托和双向止盈止损委托
+    #[serde(default)]
+    pub actual_side: String,
+    /// 策略委托触发时间
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub trigger_time: Option<i64>,
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub px_var: Option<Decimal>,
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub px_spread: Option<Decimal>,
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub sz_limit: Option<Decimal>,
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub px_limit: Option<Decimal>,
+    #[serde(default)]
+    pub time_interval: String,
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub callback_ratio: Option<Decimal>,
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub callback_spread: Option<Decimal>,
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub active_px: Option<Decimal>,
+    /// 移动止盈止损的触发价格
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub move_trigger_px: Option<Decimal>,
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub reduce_only: Option<bool>,
+    #[serde(default)]
+    pub tag: String,
+    /// 下单时的最新成交价
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub last: Option<Decimal>,
+    /// 策略委托单触发失败的错误码
+    #[serde(default)]
+    pub fail_code: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub c_time: i64,
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub u_time: Option<i64>,
+}
+
+/// 以数字字符串表示的类型，未定义的值解析为Other
+macro_rules! define_code_enum {
+    ($(#[$enum_meta:meta])* $enum_name:ident { $($(#[$meta:meta])* $name:ident = $code:expr,)* }) => {
+        $(#[$enum_meta])*
+        #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+        pub enum $enum_name {
+            $($(#[$meta])* $name,)*
+            /// 未定义的类型
+            Other(i32),
+        }
+
+        impl $enum_name {
+            pub fn from_code(code: i32) -> Self {
+                match code {
+                    $($code => $enum_name::$name,)*
+                    _ => $enum_name::Other(code),
+                }
+            }
+
+            pub fn code(&self) -> i32 {
+                match self {
+                    $($enum_name::$name => $code,)*
+                    $enum_name::Other(code) => *code,
+                }
+            }
+        }
+
+        impl std::fmt::Display for $enum_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.code())
+            }
+        }
+
+        impl std::str::FromStr for $enum_name {
+            type Err = std::num::ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(Self::from_code(s.parse()?))
+            }
+        }
+
+        impl Serialize for $enum_name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                to_str(self, serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $enum_name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                s.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+define_code_enum!(
+    /// 账单类型
+    BillType {
+        /// 划转
+        Transfer = 1,
+        /// 交易
+        Trade = 2,
+        /// 交割
+        Delivery = 3,
+        /// 自动换币
+        AutoTokenConversion = 4,
+        /// 强平
+        Liquidation = 5,
+        /// 保证金划转
+        MarginTransfer = 6,
+        /// 扣息
+        InterestDeduction = 7,
+        /// 资金费
+        FundingFee = 8,
+        /// 自动减仓
+        Adl = 9,
+        /// 穿仓补偿
+        Clawback = 10,
+        /// 系统换币
+        SystemTokenConversion = 11,
+        /// 策略划拨
+        StrategyTransfer = 12,
+        /// 对冲减仓
+        Ddh = 13,
+        /// 大宗交易
+        BlockTrade = 14,
+        /// 一键借币
+        QuickMargin = 15,
+        /// 借币
+        Borrowing = 16,
+        /// 还币
+        Repay = 22,
+        /// 价差交易
+        SpreadTrading = 24,
+        /// 结构化产品
+        StructuredProducts = 26,
+        /// 闪兑
+        Convert = 27,
+        /// 小额资产兑换
+        EasyConvert = 28,
+        /// 一键还债
+        OneClickRepay = 29,
+        /// 简单交易
+        SimpleTrade = 30,
+        /// 移仓
+        MovePosition = 32,
+        /// 借贷
+        Loans = 33,
+        /// 结算
+        Settlement = 34,
+        /// 带单分润支出
+        ProfitSharingExpenses = 250,
+        /// 带单分润退还
+        ProfitSharingRefund = 251,
+    }
+);
+
+define_code_enum!(
+    /// 账单子类型，只列出了常用的类型
+    BillSubType {
+        /// 买入
+        Buy = 1,
+        /// 卖出
+        Sell = 2,
+        /// 开多
+        OpenLong = 3,
+        /// 开空
+        OpenShort = 4,
+        /// 平多
+        CloseLong = 5,
+        /// 平空
+        CloseShort = 6,
+        /// 市场借币扣息
+        InterestDeduction = 9,
+        /// 转入
+        TransferIn = 11,
+        /// 转出
+        TransferOut = 12,
+        /// 尊享借币扣息
+        VipInterestDeduction = 14,
+        /// 强平平多
+        PartialLiquidationCloseLong = 100,
+        /// 强平平空
+        PartialLiquidationCloseShort = 101,
+        /// 强平买入
+        PartialLiquidationBuy = 102,
+        /// 强平卖出
+        PartialLiquidationSell = 103,
+        /// 强平爆仓平多
+        LiquidationLong = 104,
+        /// 强平爆仓平空
+        LiquidationShort = 105,
+        /// 强平爆仓买入
+        LiquidationBuy = 106,
+        /// 强平爆仓卖出
+        LiquidationSell = 107,
+        /// 强平穿仓补偿
+        LiquidationPenalty = 108,
+        /// 强平罚金
+        LiquidationFee = 109,
+        /// 交割平多
+        DeliveryLong = 112,
+        /// 交割平空
+        DeliverShort = 113,
+        /// 自动换币买入
+        AutoTokenConversionBuy = 114,
+        /// 自动换币卖出
+        AutoTokenConversionSell = 115,
+        /// 减仓平多
+        AdlCloseLong = 125,
+        /// 减仓平空
+        AdlCloseShort = 126,
+        /// 减仓买入
+        AdlBuy = 127,
+        /// 减仓卖出
+        AdlSell = 128,
+        /// 手动增加保证金
+        ManualMarginIncrease = 160,
+        /// 手动减少保证金
+        ManualMarginDecrease = 161,
+        /// 自动增加保证金
+        AutoMarginIncrease = 162,
+        /// 行权
+        Exercised = 170,
+        /// 被行权
+        CounterpartyExercised = 171,
+        /// 作废
+        ExpiredOtm = 172,
+        /// 资金费支出
+        FundingFeeExpense = 173,
+        /// 资金费收入
+        FundingFeeIncome = 174,
+        /// 系统转入
+        SystemTransferIn = 200,
+        /// 手动转入
+        ManualTransferIn = 201,
+        /// 系统转出
+        SystemTransferOut = 202,
+        /// 手动转出
+        ManualTransferOut = 203,
+        /// 手动借币
+        ManualBorrowing = 210,
+        /// 手动还币
+        ManualRepayment = 211,
+        /// 自动借币
+        AutoBorrow = 212,
+        /// 自动还币
+        AutoRepay = 213,
+    }
+);
+
+/// 账单流水
+/// GET /api/v5/account/bills
+/// GET /api/v5/account/bills-archive
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bill {
+    /// 不涉及产品的账单为None，如资金划转、利息
+    #[serde(default, deserialize_with="from_opt_enum")]
+    pub inst_type: Option<InstType>,
+    /// 账单ID，分页时作为after、before的参数
+    pub bill_id: String,
+    #[serde(rename = "type")]
+    pub bill_type: BillType,
+    pub sub_type: BillSubType,
+    /// 余额更新完成的时间，Unix时间戳的毫秒数格式
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub ts: i64,
+    /// 账户层面的余额变动数量
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub bal_chg: Decimal,
+    /// 仓位层面的余额变动数量
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub pos_bal_chg: Option<Decimal>,
+    /// 账户层面的余额数量
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub bal: Decimal,
+    /// 仓位层面的余额数量
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub pos_bal: Option<Decimal>,
+    /// 数量
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub sz: Option<Decimal>,
+    /// 价格，与subType相关，如成交价格、强平价格、交割价格
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub px: Option<Decimal>,
+    pub ccy: String,
+    /// 收益
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub pnl: Option<Decimal>,
+    /// 手续费，负数代表平台扣除的手续费，正数代表返佣
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub fee: Option<Decimal>,
+    /// 利息
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub interest: Option<Decimal>,
+    /// 保证金模式，不涉及保证金模式时为None
+    #[serde(default, deserialize_with="from_opt_enum")]
+    pub mgn_mode: Option<TradeMode>,
+    #[serde(default)]
+    pub inst_id: String,
+    #[serde(default)]
+    pub ord_id: String,
+    #[serde(default)]
+    pub cl_ord_id: String,
+    #[serde(default)]
+    pub trade_id: String,
+    /// 流动性方向 T：taker M：maker，不涉及成交时为空
+    pub exec_type: ExecuteType,
+    /// 转出账户 6：资金账户 18：交易账户，仅适用于资金划转
+    #[serde(default)]
+    pub from: String,
+    /// 转入账户 6：资金账户 18：交易账户，仅适用于资金划转
+    #[serde(default)]
+    pub to: String,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub tag: String,
+    /// 最新成交时间
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub fill_time: Option<i64>,
+}
+
+/// 资金划转的账户类型
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub enum AccountType {
+    /// 资金账户
+    #[serde(rename="6")]
+    Funding,
+    /// 交易账户
+    #[serde(rename="18")]
+    Trading,
+}
+
+/// 资金划转类型
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub enum TransferType {
+    /// 账户内划转
+    #[serde(rename="0")]
+    WithinAccount,
+    /// 母账户转子账户，仅适用于母账户APIKey
+    #[serde(rename="1")]
+    MasterToSub,
+    /// 子账户转母账户，仅适用于母账户APIKey
+    #[serde(rename="2")]
+    SubToMasterByMaster,
+    /// 子账户转母账户，仅适用于子账户APIKey
+    #[serde(rename="3")]
+    SubToMasterBySub,
+    /// 子账户转子账户，仅适用于子账户APIKey，且目标账户需要是同一母账户下的其他子账户
+    #[serde(rename="4")]
+    SubToSub,
+}
+
+/// 资金划转状态
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub enum TransferState {
+    #[serde(rename="success")]
+    Success,
+    /// 处理中
+    #[serde(rename="pending")]
+    Pending,
+    #[serde(rename="failed")]
+    Failed,
+}
+
+/// 资金账户余额
+/// GET /api/v5/asset/balances
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetBalance {
+    pub ccy: String,
+    /// 余额
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub bal: Decimal,
+    /// 冻结余额
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub frozen_bal: Decimal,
+    /// 可用余额
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub avail_bal: Decimal,
+}
+
+/// 币种列表，每个币种的每条链为一条记录
+/// GET /api/v5/asset/currencies
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetCurrency {
+    pub ccy: String,
+    #[serde(default)]
+    pub name: String,
+    /// 币种链信息，如 USDT-ERC20
+    pub chain: String,
+    /// 是否可充值
+    pub can_dep: bool,
+    /// 是否可提币
+    pub can_wd: bool,
+    /// 是否可内部转账
+    pub can_internal: bool,
+    /// 最小充币数量
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub min_dep: Option<Decimal>,
+    /// 最小提币数量
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub min_wd: Option<Decimal>,
+    /// 最大提币数量
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub max_wd: Option<Decimal>,
+    /// 提币精度，表示小数点后的位数
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub wd_tick_sz: Option<u32>,
+    /// 过去24小时内提币额度，单位为USD
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub wd_quota: Option<Decimal>,
+    /// 过去24小时内已用提币额度，单位为USD
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub used_wd_quota: Option<Decimal>,
+    /// 普通地址最小提币手续费数量
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub min_fee: Option<Decimal>,
+    /// 普通地址最大提币手续费数量
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub max_fee: Option<Decimal>,
+    /// 当前链是否为主链
+    #[serde(default)]
+    pub main_net: bool,
+    /// 当前链是否需要标签（tag/memo）信息
+    #[serde(default)]
+    pub need_tag: bool,
+}
+
+/// 资金划转请求
+/// POST /api/v5/asset/transfer
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TransferRequest {
+    pub ccy: String,
+    /// 划转数量
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub amt: Decimal,
+    /// 转出账户
+    pub from: AccountType,
+    /// 转入账户
+    pub to: AccountType,
+    /// 子账户名称，type为1、2、4时必填
+    #[serde(rename = "subAcct", skip_serializing_if = "Option::is_none")]
+    pub sub_acct: Option<String>,
+    #[serde(rename = "type")]
+    pub transfer_type: TransferType,
+    /// 是否支持跨币种保证金模式或组合保证金模式下的借币转出，默认为false
+    #[serde(rename = "loanTrans", skip_serializing_if = "Option::is_none")]
+    pub loan_trans: Option<bool>,
+    /// 客户自定义ID
+    /// 字母（区分大小写）与数字的组合，可以是纯字母、纯数字且长度要在1-32位之间。
+    #[serde(rename = "clientId", skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+}
+
+impl TransferRequest {
+    /// 账户内划转
+    pub fn new(ccy: &str, amt: Decimal, from: AccountType, to: AccountType) -> Self {
+        Self {
+            ccy: ccy.to_string(),
+            amt,
+            from,
+            to,
+            sub_acct: None,
+            transfer_type: TransferType::WithinAccount,
+            loan_trans: None,
+            client_id: None,
+        }
+    }
+
+    /// 资金账户转到交易账户
+    pub fn funding_to_trading(ccy: &str, amt: Decimal) -> Self {
+        Self::new(ccy, amt, AccountType::Funding, AccountType::Trading)
+    }
+
+    /// 交易账户转到资金账户
+    pub fn trading_to_funding(ccy: &str, amt: Decimal) -> Self {
+        Self::new(ccy, amt, AccountType::Trading, AccountType::Funding)
+    }
+
+    /// 母子账户间划转，transfer_type为MasterToSub、SubToMasterByMaster、SubToSub时sub_acct必填
+    pub fn with_sub_account(mut self, transfer_type: TransferType, sub_acct: Option<String>) -> Self {
+        self.transfer_type = transfer_type;
+        self.sub_acct = sub_acct;
+        self
+    }
+
+    pub fn with_client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.client_id = Some(client_id.into());
+        self
+    }
+}
+
+/// 资金划转结果
+/// POST /api/v5/asset/transfer
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetTransfer {
+    /// 划转ID
+    pub trans_id: String,
+    pub ccy: String,
+    #[serde(default)]
+    pub client_id: String,
+    pub from: AccountType,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub amt: Decimal,
+    pub to: AccountType,
+}
+
+/// 资金划转状态
+/// GET /api/v5/asset/transfer-state
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetTransferState {
+    pub trans_id: String,
+    #[serde(default)]
+    pub client_id: String,
+    pub ccy: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub amt: Decimal,
+    #[serde(rename = "type")]
+    pub transfer_type: TransferType,
+    pub from: AccountType,
+    pub to: AccountType,
+    /// 子账户名称
+    #[serde(default)]
+    pub sub_acct: String,
+    pub state: TransferState,
+}
+
+define_code_enum!(
+    /// 资金账户账单类型，只列出了常用的类型
+    AssetBillType {
+        /// 充值
+        Deposit = 1,
+        /// 提现
+        Withdrawal = 2,
+        /// 撤销提现
+        CanceledWithdrawal = 13,
+        /// 转出至子账户（母账户视角）
+        TransferToSubAccount = 20,
+        /// 从子账户转入（母账户视角）
+        TransferFromSubAccount = 21,
+        /// 转出到母账户（子账户视角）
+        TransferOutFromSubToMaster = 22,
+        /// 母账户转入（子账户视角）
+        TransferInFromMasterToSub = 23,
+        /// 从交易账户转入
+        TransferredFromTradingAccount = 130,
+        /// 转出至交易账户
+        TransferredToTradingAccount = 131,
+    }
+);
+
+/// 资金流水
+/// GET /api/v5/asset/bills
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetBill {
+    pub bill_id: String,
+    pub ccy: String,
+    /// 转账或提币的客户自定义ID
+    #[serde(default)]
+    pub client_id: String,
+    /// 账户层面的余额变动数量
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub bal_chg: Decimal,
+    /// 账户层面的余额数量
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub bal: Decimal,
+    #[serde(rename = "type")]
+    pub bill_type: AssetBillType,
+    /// 账单创建时间，Unix时间戳的毫秒数格式
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub ts: i64,
+}
+
+/// 账户资产估值
+/// GET /api/v5/asset/asset-valuation
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetValuation {
+    /// 账户总资产估值
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub total_bal: Decimal,
+    /// 数据更新时间，Unix时间戳的毫秒数格式
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub ts: i64,
+    /// 各个账户的资产估值
+    pub details: AssetValuationDetails,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetValuationDetails {
+    /// 资金账户
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub funding: Decimal,
+    /// 交易账户
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub trading: Decimal,
+    /// 经典账户，已停用
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub classic: Option<Decimal>,
+    /// 金融账户
+    #[serde(default, serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub earn: Option<Decimal>,
+}
+
+/// 充值地址
+/// GET /api/v5/asset/deposit-address
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositAddress {
+    /// 充值地址
+    pub addr: String,
+    /// 部分币种充值需要标签
+    #[serde(default)]
+    pub tag: String,
+    /// 部分币种充值需要memo
+    #[serde(default)]
+    pub memo: String,
+    /// 部分币种充值需要pmtId
+    #[serde(default)]
+    pub pmt_id: String,
+    pub ccy: String,
+    /// 币种链信息，如 USDT-ERC20
+    pub chain: String,
+    /// 转入账户
+    pub to: AccountType,
+    /// 该地址是否为页面选中的地址
+    #[serde(default)]
+    pub selected: bool,
+    /// 合约地址后6位
+    #[serde(default)]
+    pub ct_addr: String,
+}
+
+define_code_enum!(
+    /// 充值状态
+    DepositState {
+        /// 等待确认
+        WaitingForConfirmation = 0,
+        /// 确认到账
+        Credited = 1,
+        /// 充值成功
+        Successful = 2,
+        /// 临时币种充值暂停导致的挂起
+        Pending = 8,
+        /// 命中地址黑名单
+        MatchAddressBlacklist = 11,
+        /// 账户或充值被冻结
+        AccountOrDepositFrozen = 12,
+        /// 子账户充值拦截
+        SubAccountDepositInterception = 13,
+        /// KYC限额
+        KycLimit = 14,
+    }
+);
+
+/// 充值记录
+/// GET /api/v5/asset/deposit-history
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositHistory {
+    pub ccy: String,
+    pub chain: String,
+    /// 充值数量
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub amt: Decimal,
+    /// 充值账户，只显示内部账户转账地址，不显示区块链充值地址
+    #[serde(default)]
+    pub from: String,
+    /// 到账地址
+    pub to: String,
+    /// 区块转账哈希记录
+    #[serde(default)]
+    pub tx_id: String,
+    /// 充值记录创建时间，Unix时间戳的毫秒数格式
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub ts: i64,
+    pub state: DepositState,
+    /// 充值记录ID
+    pub dep_id: String,
+    /// 内部转账发起者提币申请ID，如果该笔充值来自于内部转账，则该字段展示内部转账发起者的提币申请ID
+    #[serde(default)]
+    pub from_wd_id: String,
+}
+
+/// 提币方式
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub enum WithdrawalDest {
+    /// 内部转账
+    #[serde(rename="3")]
+    Internal,
+    /// 链上提币
+    #[serde(rename="4")]
+    OnChain,
+}
+
+define_code_enum!(
+    /// 提币状态
+    WithdrawalState {
+        /// 撤销中
+        Canceling = -3,
+        /// 已撤销
+        Canceled = -2,
+        /// 失败
+        Failed = -1,
+        /// 等待提币
+        WaitingWithdrawal = 0,
+        /// 提币中
+        Broadcasting = 1,
+        /// 提币成功
+        Success = 2,
+        /// 人工审核中
+        WaitingManualReview = 4,
+        /// 已审核通过
+        Approved = 7,
+        /// 等待划转
+        WaitingTransfer = 10,
+        /// 等待交易验证
+        PendingTransactionValidation = 15,
+        /// 根据当地法律法规，提币可能最多需要24小时到账
+        Delayed = 16,
+    }
+);
+
+impl WithdrawalState {
+    /// 是否为最终状态：已撤销、失败或成功
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, WithdrawalState::Canceled | WithdrawalState::Failed | WithdrawalState::Success)
+    }
+}
+
+/// 提币请求
+/// POST /api/v5/asset/withdrawal
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalRequest {
+    pub ccy: String,
+    /// 提币数量
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub amt: Decimal,
+    pub dest: WithdrawalDest,
+    /// 链上提币时为认证过的数字货币地址，部分币种需要使用 地址:标签 的格式；内部转账时为手机号、邮箱或账户名
+    pub to_addr: String,
+    /// 币种链信息，如 USDT-ERC20，不填时默认为主链
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<String>,
+    /// 手机区号，当toAddr为手机号时必填
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub area_code: Option<String>,
+    /// 客户自定义ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+}
+
+impl WithdrawalRequest {
+    /// 链上提币
+    pub fn on_chain(ccy: &str, amt: Decimal, to_addr: &str, chain: &str) -> Self {
+        Self {
+            ccy: ccy.to_string(),
+            amt,
+            dest: WithdrawalDest::OnChain,
+            to_addr: to_addr.to_string(),
+            chain: Some(chain.to_string()),
+            area_code: None,
+            client_id: None,
+        }
+    }
+
+    /// 内部转账，to_addr为手机号、邮箱或账户名
+    pub fn internal(ccy: &str, amt: Decimal, to_addr: &str) -> Self {
+        Self {
+            ccy: ccy.to_string(),
+            amt,
+            dest: WithdrawalDest::Internal,
+            to_addr: to_addr.to_string(),
+            chain: None,
+            area_code: None,
+            client_id: None,
+        }
+    }
+
+    pub fn with_client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.client_id = Some(client_id.into());
+        self
+    }
+}
+
+/// 提币结果
+/// POST /api/v5/asset/withdrawal
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Withdrawal {
+    pub ccy: String,
+    #[serde(default)]
+    pub chain: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub amt: Decimal,
+    /// 提币申请ID
+    pub wd_id: String,
+    #[serde(default)]
+    pub client_id: String,
+}
+
+/// 撤销提币结果
+/// POST /api/v5/asset/cancel-withdrawal
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelWithdrawal {
+    pub wd_id: String,
+}
+
+/// 提币记录
+/// GET /api/v5/asset/withdrawal-history
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalHistory {
+    pub ccy: String,
+    pub chain: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub amt: Decimal,
+    /// 提币申请时间，Unix时间戳的毫秒数格式
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub ts: i64,
+    /// 提币账户，可以是邮箱、手机号或账户名
+    #[serde(default)]
+    pub from: String,
+    /// 收币地址
+    pub to: String,
+    #[serde(default)]
+    pub tag: String,
+    #[serde(default)]
+    pub pmt_id: String,
+    #[serde(default)]
+    pub memo: String,
+    /// 提币哈希记录，内部转账时为空
+    #[serde(default)]
+    pub tx_id: String,
+    /// 提币手续费数量
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub fee: Decimal,
+    #[serde(default)]
+    pub fee_ccy: String,
+    pub state: WithdrawalState,
+    pub wd_id: String,
+    #[serde(default)]
+    pub client_id: String,
+}
+
+macro_rules! impl_to_str {
+    ($($arg:tt)*) => {
+        $(
+        impl From<$arg> for String {
+            fn from(value: $arg) -> String {
+                let str = serde_json::to_string(&value).unwrap();
+                serde_json::from_str(&str).unwrap()
+            }
+        }
+        )*
+    };
+}
+
+impl_to_str!(
+    TradeMode
+    TradeSide
+    PositionSide
+    OrderType
+    StopMode
+    TpTriggerPxType
     QuickMgnType
     OrderState
     InstType
     InstrumentState
     ExecuteType
-);
\ No newline at end of file
+    Bar
+    SystemState
+    AlgoOrderType
+    AlgoOrderState
+    PositionMode
+    IsoMode
+    IsoModeType
+    MarginBalanceType
+    GreeksType
+    AccountType
+    TransferType
+    WithdrawalDest
+);
+
+impl_sub_result!(
+    TradeCancelBatchOrders
+    TradeOrder
+    TradeAmendOrder
+    TradeOrderAlgo
+    TradeCancelAlgos
+    TradeAmendAlgos
+);
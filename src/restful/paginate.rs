@@ -0,0 +1,51 @@
+use std::future::Future;
+use std::sync::Arc;
+use futures_util::{stream, Stream, StreamExt};
+use crate::api_enum::APiEnum;
+use crate::apikey::OkxAccountClient;
+use crate::okx_error::*;
+
+/// okx分页接口每页最多返回的数量
+const MAX_PAGE_SIZE: usize = 100;
+
+impl OkxAccountClient {
+    /// 从after开始按billId向前翻页，依次返回全部记录，顺序为从新到旧
+    /// fetch按after请求一页，返回的数量小于page_size时结束，page_size超过每页上限100时按100计算
+    /// 每页请求前会等待限速额度，请求失败时返回错误并结束
+    pub(crate) fn bill_id_stream<'a, T, F, Fut>(
+        &'a self,
+        api: APiEnum,
+        page_size: usize,
+        after: Option<String>,
+        bill_id: fn(&T) -> &str,
+        fetch: F,
+    ) -> impl Stream<Item = Result<T>> + 'a
+    where
+        T: 'a,
+        F: Fn(Option<String>) -> Fut + 'a,
+        Fut: Future<Output = Result<Vec<T>>> + 'a,
+    {
+        let page_size = page_size.min(MAX_PAGE_SIZE);
+        let fetch = Arc::new(fetch);
+        stream::unfold(Some(after), move |after: Option<Option<String>>| {
+            let fetch = fetch.clone();
+            async move {
+                let after = after?;
+                let page = match self.limit_mgr().wait_limit(api, 1).await {
+                    Ok(_) => fetch(after).await,
+                    Err(err) => Err(err),
+                };
+                let page = match page {
+                    Ok(page) => page,
+                    Err(err) => return Some((vec![Err(err)], None)),
+                };
+
+                let next = match page.last() {
+                    Some(last) if page.len() >= page_size => Some(Some(bill_id(last).to_string())),
+                    _ => None,
+                };
+                Some((page.into_iter().map(Ok).collect::<Vec<_>>(), next))
+            }
+        }).flat_map(stream::iter)
+    }
+}
@@ -1,9 +1,45 @@
 use std::collections::BTreeMap;
+use std::time::Duration;
+use log::warn;
 use crate::api_enum::APiEnum;
 use crate::okx_error::*;
-use super::models::{Instrument, RestApi};
+use serde::{Deserialize, Serialize};
+use super::models::{EstimatedPrice, FundingRate, FundingRateHistory, Instrument, InsuranceFund, MarkPrice, OpenInterest, PositionTier, PriceLimit, PublicTime, RestApi, SystemState, SystemStatus};
 use crate::apikey::OkxPublicClient;
-use crate::InstType;
+use crate::{InstType, TradeMode};
+use crate::utils::get_unix;
+
+#[derive(Serialize, Debug, Deserialize, Default, Clone)]
+pub struct InsuranceFundFilter {
+    #[serde(rename="instType")]
+    pub inst_type: Option<InstType>,
+    /// 风险准备金类型 regular_update liquidation_balance_deposit bankruptcy_loss platform_revenue adl
+    #[serde(rename="type")]
+    pub fund_type: Option<String>,
+    pub uly: Option<String>,
+    #[serde(rename="instFamily")]
+    pub inst_family: Option<String>,
+    /// 币种，仅适用于币币杠杆
+    pub ccy: Option<String>,
+    pub before: Option<i64>,
+    pub after: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+/// 仓位档位的查询条件
+/// 交割、永续和期权需要uly或inst_family，币币杠杆需要inst_id或ccy
+#[derive(Serialize, Debug, Deserialize, Default, Clone)]
+pub struct PositionTiersFilter {
+    pub uly: Option<String>,
+    #[serde(rename="instFamily")]
+    pub inst_family: Option<String>,
+    #[serde(rename="instId")]
+    pub inst_id: Option<String>,
+    /// 保证金币种，仅适用于杠杆全仓
+    pub ccy: Option<String>,
+    /// 查指定档位
+    pub tier: Option<u32>,
+}
 
 impl OkxPublicClient {
     //     获取交易产品基础信息
@@ -20,7 +56,7 @@ impl OkxPublicClient {
     ) -> Result<Vec<Instrument>>
     {
         //  /api/index/v3/BTC-USD/constituents
-        self.limit_mgr().check_limit_with_inst_type(APiEnum::PublicInstruments as u32, inst_type, 1, 20, 2)?;
+        self.limit_mgr().check_limit_with_inst_type(APiEnum::PublicInstruments, inst_type, 1).await?;
         let mut params: BTreeMap<String, String> = BTreeMap::new();
 
         if let Some(uly) = uly {
@@ -41,16 +77,371 @@ impl OkxPublicClient {
             .get::<RestApi<Instrument>>("/api/v5/public/instruments", &params)
             .await?.to_result()
     }
+
+    /// 获取永续合约当前资金费率
+    /// 限速：20次/2s
+    /// 限速规则：IP + Instrument ID
+    /// GET /api/v5/public/funding-rate
+    pub async fn public_funding_rate(&self, inst_id: impl Into<String>) -> Result<FundingRate> {
+        let inst_id = inst_id.into();
+        self.limit_mgr().check_limit_with_inst_id(APiEnum::PublicFundingRate, &inst_id, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        params.insert("instId".into(), inst_id);
+
+        self
+            .get::<RestApi<FundingRate>>("/api/v5/public/funding-rate", &params)
+            .await?.to_result_one()
+    }
+
+    /// 获取最近3个月的历史资金费率，按时间倒序返回
+    /// 限速：10次/2s
+    /// 限速规则：IP + Instrument ID
+    /// GET /api/v5/public/funding-rate-history
+    pub async fn public_funding_rate_history(
+        &self,
+        inst_id: impl Into<String>,
+        before: Option<i64>,
+        after: Option<i64>,
+        limit: Option<usize>,
+    ) -> Result<Vec<FundingRateHistory>> {
+        let inst_id = inst_id.into();
+        self.limit_mgr().check_limit_with_inst_id(APiEnum::PublicFundingRateHistory, &inst_id, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        params.insert("instId".into(), inst_id);
+        if let Some(before) = before {
+            params.insert("before".into(), before.to_string());
+        }
+        if let Some(after) = after {
+            params.insert("after".into(), after.to_string());
+        }
+        if let Some(limit) = limit {
+            params.insert("limit".into(), limit.to_string());
+        }
+
+        self
+            .get::<RestApi<FundingRateHistory>>("/api/v5/public/funding-rate-history", &params)
+            .await?.to_result()
+    }
+
+    /// 获取持仓总量
+    /// 限速：20次/2s
+    /// 限速规则：IP + Instrument ID，不传inst_id时按产品类型共用额度
+    /// GET /api/v5/public/open-interest
+    pub async fn public_open_interest(
+        &self,
+        inst_type: InstType,
+        uly: Option<impl Into<String>>,
+        inst_family: Option<impl Into<String>>,
+        inst_id: Option<impl Into<String>>,
+    ) -> Result<Vec<OpenInterest>> {
+        let params = Self::derivative_params(inst_type, uly, inst_family, inst_id);
+        match params.get("instId") {
+            Some(inst_id) => self.limit_mgr().check_limit_with_inst_id(APiEnum::PublicOpenInterest, inst_id, 1).await?,
+            None => self.limit_mgr().check_limit_with_inst_type(APiEnum::PublicOpenInterest, inst_type, 1).await?,
+        }
+
+        self
+            .get::<RestApi<OpenInterest>>("/api/v5/public/open-interest", &params)
+            .await?.to_result()
+    }
+
+    /// 获取标记价格
+    /// 限速：10次/2s
+    /// 限速规则：IP + Instrument ID，不传inst_id时按产品类型共用额度
+    /// GET /api/v5/public/mark-price
+    pub async fn public_mark_price(
+        &self,
+        inst_type: InstType,
+        uly: Option<impl Into<String>>,
+        inst_family: Option<impl Into<String>>,
+        inst_id: Option<impl Into<String>>,
+    ) -> Result<Vec<MarkPrice>> {
+        let params = Self::derivative_params(inst_type, uly, inst_family, inst_id);
+        match params.get("instId") {
+            Some(inst_id) => self.limit_mgr().check_limit_with_inst_id(APiEnum::PublicMarkPrice, inst_id, 1).await?,
+            None => self.limit_mgr().check_limit_with_inst_type(APiEnum::PublicMarkPrice, inst_type, 1).await?,
+        }
+
+        self
+            .get::<RestApi<MarkPrice>>("/api/v5/public/mark-price", &params)
+            .await?.to_result()
+    }
+
+    /// 查询单个交易产品的最高买价和最低卖价
+    /// 限速：20次/2s
+    /// 限速规则：IP
+    /// GET /api/v5/public/price-limit
+    pub async fn public_price_limit(&self, inst_id: impl Into<String>) -> Result<PriceLimit> {
+        self.limit_mgr().check_limit(APiEnum::PublicPriceLimit, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        params.insert("instId".into(), inst_id.into());
+
+        self
+            .get::<RestApi<PriceLimit>>("/api/v5/public/price-limit", &params)
+            .await?.to_result_one()
+    }
+
+    /// 获取交割合约和期权预估交割/行权价，只有交割/行权预估结算前一小时才有返回值
+    /// 限速：10次/2s
+    /// 限速规则：IP + Instrument ID
+    /// GET /api/v5/public/estimated-price
+    pub async fn public_estimated_price(&self, inst_id: impl Into<String>) -> Result<Option<EstimatedPrice>> {
+        let inst_id = inst_id.into();
+        self.limit_mgr().check_limit_with_inst_id(APiEnum::PublicEstimatedPrice, &inst_id, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        params.insert("instId".into(), inst_id);
+
+        self
+            .get::<RestApi<EstimatedPrice>>("/api/v5/public/estimated-price", &params)
+            .await?.to_result_one_opt()
+    }
+
+    /// 获取全部仓位档位对应信息
+    /// 限速：10次/2s
+    /// 限速规则：IP
+    /// GET /api/v5/public/position-tiers
+    pub async fn public_position_tiers(&self, inst_type: InstType, td_mode: TradeMode, filter: PositionTiersFilter) -> Result<Vec<PositionTier>> {
+        self.limit_mgr().check_limit(APiEnum::PublicPositionTiers, 1).await?;
+        let mut params = Self::derivative_params(inst_type, filter.uly, filter.inst_family, filter.inst_id);
+
+        params.insert("tdMode".into(), td_mode.into());
+        if let Some(ccy) = filter.ccy {
+            params.insert("ccy".into(), ccy);
+        }
+        if let Some(tier) = filter.tier {
+            params.insert("tier".into(), tier.to_string());
+        }
+
+        self
+            .get::<RestApi<PositionTier>>("/api/v5/public/position-tiers", &params)
+            .await?.to_result()
+    }
+
+    /// 获取风险准备金余额信息
+    /// 限速：10次/2s
+    /// 限速规则：IP
+    /// GET /api/v5/public/insurance-fund
+    pub async fn public_insurance_fund(&self, filter: InsuranceFundFilter) -> Result<Vec<InsuranceFund>> {
+        self.limit_mgr().check_limit(APiEnum::PublicInsuranceFund, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(inst_type) = filter.inst_type {
+            params.insert("instType".into(), inst_type.into());
+        }
+        if let Some(fund_type) = filter.fund_type {
+            params.insert("type".into(), fund_type);
+        }
+        if let Some(uly) = filter.uly {
+            params.insert("uly".into(), uly);
+        }
+        if let Some(inst_family) = filter.inst_family {
+            params.insert("instFamily".into(), inst_family);
+        }
+        if let Some(ccy) = filter.ccy {
+            params.insert("ccy".into(), ccy);
+        }
+        if let Some(before) = filter.before {
+            params.insert("before".into(), before.to_string());
+        }
+        if let Some(after) = filter.after {
+            params.insert("after".into(), after.to_string());
+        }
+        if let Some(limit) = filter.limit {
+            params.insert("limit".into(), limit.to_string());
+        }
+
+        self
+            .get::<RestApi<InsuranceFund>>("/api/v5/public/insurance-fund", &params)
+            .await?.to_result()
+    }
+
+    /// 获取标的指数，如 BTC-USD
+    /// 限速：20次/2s
+    /// 限速规则：IP
+    /// GET /api/v5/public/underlying
+    pub async fn public_underlying(&self, inst_type: InstType) -> Result<Vec<String>> {
+        self.limit_mgr().check_limit(APiEnum::PublicUnderlying, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        params.insert("instType".into(), inst_type.into());
+
+        self
+            .get::<RestApi<Vec<String>>>("/api/v5/public/underlying", &params)
+            .await?.to_result_one()
+    }
+
+    /// 获取系统时间，Unix时间戳的毫秒数格式
+    /// 限速：10次/2s
+    /// 限速规则：IP
+    /// GET /api/v5/public/time
+    pub async fn public_time(&self) -> Result<i64> {
+        self.limit_mgr().check_limit(APiEnum::PublicTime, 1).await?;
+        self.get_public_time().await
+    }
+
+    /// 不检查限速，调用前需要先申请额度
+    async fn get_public_time(&self) -> Result<i64> {
+        let params: BTreeMap<String, String> = BTreeMap::new();
+
+        self
+            .get::<RestApi<PublicTime>>("/api/v5/public/time", &params)
+            .await?.to_result_one().map(|item| item.ts)
+    }
+
+    /// 获取系统升级事件的状态，state为空时返回等待中、进行中和预开放的事件
+    /// 限速：1次/5s
+    /// 限速规则：IP
+    /// GET /api/v5/system/status
+    pub async fn system_status(&self, state: Option<SystemState>) -> Result<Vec<SystemStatus>> {
+        self.limit_mgr().check_limit(APiEnum::SystemStatus, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(state) = state {
+            params.insert("state".into(), state.into());
+        }
+
+        self
+            .get::<RestApi<SystemStatus>>("/api/v5/system/status", &params)
+            .await?.to_result()
+    }
+
+    /// 按服务器时间校正本地时钟偏差，返回新的偏差，单位：毫秒
+    /// 校正后的偏差会用于rest签名和websocket登录
+    pub async fn sync_time(&self) -> Result<i64> {
+        // 先申请额度，等待限速的时间不计入往返耗时
+        self.limit_mgr().check_limit(APiEnum::PublicTime, 1).await?;
+        let send_time = get_unix();
+        let server_time = self.get_public_time().await?;
+        let recv_time = get_unix();
+        Ok(self.time_sync().update(send_time, recv_time, server_time))
+    }
+
+    /// 启动后台任务，每隔interval校正一次时钟偏差
+    /// 账户客户端可通过with_time_sync(pub_client.time_sync().clone())共用校正结果
+    pub fn start_time_sync(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let client = self.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(err) = client.sync_time().await {
+                    warn!("sync time error:{}", err);
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })
+    }
+
+    fn derivative_params(
+        inst_type: InstType,
+        uly: Option<impl Into<String>>,
+        inst_family: Option<impl Into<String>>,
+        inst_id: Option<impl Into<String>>,
+    ) -> BTreeMap<String, String> {
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        params.insert("instType".into(), inst_type.into());
+        if let Some(uly) = uly {
+            params.insert("uly".into(), uly.into());
+        }
+        if let Some(inst_family) = inst_family {
+            params.insert("instFamily".into(), inst_family.into());
+        }
+        if let Some(inst_id) = inst_id {
+            params.insert("instId".into(), inst_id.into());
+        }
+        params
+    }
 }
 
 #[cfg(test)]
 mod test{
-    use crate::InstType;
+    use std::sync::Arc;
+    use rust_decimal::Decimal;
+    use crate::{InstType, InstrumentState, LimitKey, MockTransport, OkxError, OkxErrorCode, OkxPublicClient};
+
+    const INSTRUMENTS_RESP: &str = r#"{"code":"0","msg":"","data":[{"alias":"","baseCcy":"BTC","category":"1","ctMult":"","ctType":"","ctVal":"","ctValCcy":"","expTime":"","instFamily":"","instId":"BTC-USDT","instType":"SPOT","lever":"10","listTime":"1606468572000","lotSz":"0.00000001","maxIcebergSz":"9999999999.0000000000000000","maxLmtSz":"9999999999","maxMktSz":"1000000","maxStopSz":"1000000","maxTriggerSz":"9999999999.0000000000000000","maxTwapSz":"9999999999.0000000000000000","minSz":"0.00001","optType":"","quoteCcy":"USDT","settleCcy":"","state":"live","stk":"","tickSz":"0.1","uly":""}]}"#;
 
     #[tokio::test]
     pub async fn test_instrument() {
-        let pub_client = crate::testnet_config().create_pub_client();
-        let result = pub_client.public_instruments(InstType::Spot, None, None, None).await.unwrap();
-        println!("result:{:?}", result);
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(INSTRUMENTS_RESP);
+        let pub_client = OkxPublicClient::with_transport(crate::testnet_config(), transport.clone());
+
+        let result = pub_client.public_instruments(InstType::Spot, None::<String>, None::<String>, None::<String>).await.unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].inst_id, "BTC-USDT");
+        assert_eq!(result[0].state, InstrumentState::Live);
+        assert_eq!(result[0].list_time, Some(1606468572000));
+        assert_eq!(result[0].lot_sz, Decimal::new(1, 8));
+        assert_eq!(result[0].tick_sz.to_string(), "0.1");
+        assert_eq!(result[0].ct_val, None);
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, http::Method::GET);
+        assert_eq!(requests[0].url, "https://www.okx.com/api/v5/public/instruments?instType=SPOT");
+    }
+
+    #[tokio::test]
+    pub async fn test_instrument_remote_error() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(r#"{"code":"51001","msg":"Instrument ID does not exist","data":[]}"#);
+        let pub_client = OkxPublicClient::with_transport(crate::testnet_config(), transport);
+
+        let result = pub_client.public_instruments(InstType::Spot, None::<String>, None::<String>, Some("ABC-USDT")).await;
+        assert!(matches!(result, Err(OkxError::RemoteError { code: OkxErrorCode::InstrumentNotExist, .. })));
+    }
+
+    #[tokio::test]
+    pub async fn test_funding_rate() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"fundingRate":"0.0000792386885340","fundingTime":"1703088000000","instId":"BTC-USDT-SWAP",
+            "instType":"SWAP","method":"next_period","maxFundingRate":"0.00375","minFundingRate":"-0.00375","nextFundingRate":"0.0002061194322149",
+            "nextFundingTime":"1703116800000","premium":"0.0001233824646391","settFundingRate":"0.0001418433662153","settState":"settled","ts":"1703070685309"}]}"#);
+        transport.push_json(r#"{"code":"0","msg":"","data":[["LTC-USD","BTC-USD","ETC-USD"]]}"#);
+        let pub_client = OkxPublicClient::with_transport(crate::testnet_config(), transport.clone());
+
+        let result = pub_client.public_funding_rate("BTC-USDT-SWAP").await.unwrap();
+        assert_eq!(result.inst_type, InstType::Swap);
+        assert_eq!(result.funding_time, 1703088000000);
+        assert_eq!(result.max_funding_rate, Some(Decimal::new(375, 5)));
+
+        let result = pub_client.public_underlying(InstType::Futures).await.unwrap();
+        assert_eq!(result, vec!["LTC-USD", "BTC-USD", "ETC-USD"]);
+        assert_eq!(transport.requests()[1].url, "https://www.okx.com/api/v5/public/underlying?instType=FUTURES");
+    }
+
+    #[tokio::test]
+    pub async fn test_mark_price_limit_key() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"instId":"BTC-USDT-SWAP","instType":"SWAP","markPx":"42310.6","ts":"1703070685309"}]}"#);
+        let pub_client = OkxPublicClient::with_transport(crate::testnet_config(), transport.clone());
+
+        // 不传inst_id时按产品类型限速，不与产品id共用维度
+        pub_client.public_mark_price(InstType::Swap, None::<String>, None::<String>, None::<String>).await.unwrap();
+        let status = pub_client.rate_limit_status().unwrap();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].slot.scope, LimitKey::InstType);
+        assert_eq!(status[0].slot.key, "SWAP");
+    }
+
+    #[tokio::test]
+    pub async fn test_sync_time() {
+        let transport = Arc::new(MockTransport::new());
+        let server_time = crate::utils::get_unix() + 60_000;
+        transport.push_json(format!(r#"{{"code":"0","msg":"","data":[{{"ts":"{}"}}]}}"#, server_time));
+        let pub_client = OkxPublicClient::with_transport(crate::testnet_config(), transport.clone());
+
+        let offset = pub_client.sync_time().await.unwrap();
+        assert!((59_000..=61_000).contains(&offset));
+        assert_eq!(pub_client.time_sync().offset_ms(), offset);
+
+        let account_client = crate::OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport.clone())
+            .with_time_sync(pub_client.time_sync().clone());
+        let timestamp = chrono::DateTime::parse_from_rfc3339(&account_client.get_timestamp()).unwrap();
+        assert!(timestamp.timestamp_millis() - crate::utils::get_unix() >= 59_000);
     }
 }
@@ -0,0 +1,103 @@
+use std::time::Duration;
+use crate::okx_error::OkxError;
+use crate::utils::get_unix_nano;
+
+/// 哪些错误需要重试
+#[derive(Debug, Clone, Copy)]
+pub struct RetryOn {
+    /// 网络错误，如连接失败、请求超时
+    pub transport: bool,
+    /// 服务端错误，如http 5xx、okx返回系统繁忙
+    pub server_error: bool,
+    /// okx返回的限速错误，如http 429、50011
+    pub rate_limit: bool,
+}
+
+impl Default for RetryOn {
+    fn default() -> Self {
+        Self {
+            transport: true,
+            server_error: true,
+            rate_limit: true,
+        }
+    }
+}
+
+/// rest请求的重试策略
+/// 只会应用到幂等的请求上：所有GET请求，以及设置了clOrdId的下单请求
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// 最多请求次数，包含第一次请求。1表示不重试
+    pub max_attempts: u32,
+    /// 第一次重试前的等待时间，之后每次翻倍
+    pub base_delay: Duration,
+    /// 单次等待的最长时间
+    pub max_delay: Duration,
+    /// 是否在等待时间上增加随机抖动，避免多个客户端同时重试
+    pub jitter: bool,
+    pub retry_on: RetryOn,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(2),
+            jitter: true,
+            retry_on: RetryOn::default(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 不重试
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// 第attempt次请求失败后是否需要重试，attempt从1开始
+    pub fn should_retry(&self, attempt: u32, err: &OkxError) -> bool {
+        if attempt >= self.max_attempts {
+            return false;
+        }
+
+        let retry_on = &self.retry_on;
+        match err {
+            OkxError::ReqwestError(err) => retry_on.transport && (err.is_timeout() || err.is_connect()),
+            OkxError::TransportError(_) => retry_on.transport,
+            _ if err.is_rate_limit() => retry_on.rate_limit,
+            _ if err.is_retryable() => retry_on.server_error,
+            _ => false,
+        }
+    }
+
+    /// 第attempt次请求失败后的等待时间
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        if !self.jitter {
+            return delay;
+        }
+
+        // 在[delay/2, delay]之间随机
+        let half = delay / 2;
+        let jitter_ms = half.as_millis() as u64;
+        if jitter_ms == 0 {
+            return delay;
+        }
+        half + Duration::from_millis(random() % (jitter_ms + 1))
+    }
+}
+
+fn random() -> u64 {
+    // xorshift，用当前时间做种子，只用于抖动
+    let mut val = get_unix_nano() as u64 | 1;
+    val ^= val << 13;
+    val ^= val >> 7;
+    val ^= val << 17;
+    val
+}
@@ -1,244 +1,299 @@
 use std::collections::BTreeMap;
 use std::fmt::Debug;
 use crate::okx_error::*;
-use http::{HeaderMap, HeaderValue};
-use log::debug;
+use http::{HeaderMap, HeaderValue, Method};
+use http::header::CONTENT_TYPE;
+use log::{debug, warn};
 use ring::hmac;
 use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+use crate::apikey::{OkxAccountClient, OkxConfig, OkxPublicClient};
+use crate::okx_error::OkxErrorCode;
+use crate::restful::retry::RetryPolicy;
+use crate::restful::transport::{HttpRequest, HttpResponse, HttpTransport};
+use crate::utils::from_str;
+use crate::utils::time_sync::TimeSync;
+
+/// 一次rest请求的描述，接口只需要声明路径、参数和返回类型
+#[derive(Clone, Debug)]
+pub struct RestRequest {
+    method: Method,
+    path: String,
+    query: BTreeMap<String, String>,
+    body: Option<String>,
+    /// 是否可以安全地重试
+    idempotent: bool,
+}
 
-use crate::apikey::{OkxAccountClient, OkxPublicClient};
+impl RestRequest {
+    pub fn get(path: &str, query: &BTreeMap<String, String>) -> Self {
+        Self {
+            method: Method::GET,
+            path: path.to_string(),
+            query: query.clone(),
+            body: None,
+            idempotent: true,
+        }
+    }
 
-impl OkxAccountClient {
-    pub async fn get<T>(
-        &self,
-        request_path: &str,
-        parameters: &BTreeMap<String, String>,
-    ) -> Result<T>
-    where
-        T: DeserializeOwned + std::fmt::Debug,
-    {
-        // # 获取本地时间
-        let timestamp = self.get_timestamp();
+    pub fn post(path: &str, body: &impl Serialize) -> Result<Self> {
+        Ok(Self {
+            method: Method::POST,
+            path: path.to_string(),
+            query: BTreeMap::new(),
+            body: Some(serde_json::to_string(body)?),
+            idempotent: false,
+        })
+    }
 
-        let mut get_url_params: String = String::from(request_path);
+    /// 设置请求是否可以安全地重试，GET请求默认可以，POST请求默认不可以
+    pub fn idempotent(mut self, idempotent: bool) -> Self {
+        self.idempotent = idempotent;
+        self
+    }
 
-        // 有数据就格式化参数
-        if parameters.len() != 0 {
-            get_url_params = format!("{}?{}", request_path, self.parse_params_to_str(parameters));
+    /// 带查询参数的请求路径，签名和发送都使用它
+    fn request_path(&self) -> String {
+        if self.query.is_empty() {
+            return self.path.clone();
         }
-        // println!("url {}",get_url_params);
-        // OK-ACCESS-SIGN的请求头是对timestamp + method + requestPath + body字符串(+表示字符串连接)，以及SecretKey
-
-        // let message = format!("{}GET{}{}", timestamp, get_url_params, &self.secret_key);
-        let message = format!("{}GET{}", timestamp, get_url_params);
-
-        // println!("{:?}", message);
 
-        let sign = self.sign(&message);
+        let query = self
+            .query
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<String>>()
+            .join("&");
+        format!("{}?{}", self.path, query)
+    }
+}
 
-        let headers = self.create_header(&sign, &timestamp);
+/// 签名用的api key信息
+#[derive(Clone, Copy)]
+pub(crate) struct Credentials<'a> {
+    pub api_key: &'a str,
+    pub secret_key: &'a str,
+    pub passphrase: &'a str,
+}
 
-        let client = reqwest::Client::new();
+impl Credentials<'_> {
+    // 做签名
+    // OK-ACCESS-SIGN的请求头是对timestamp + method + requestPath + body字符串(+表示字符串连接)，以及SecretKey
+    fn sign(&self, message: &str) -> String {
+        let hmac_key = hmac::Key::new(hmac::HMAC_SHA256, self.secret_key.as_bytes());
+        let result = hmac::sign(&hmac_key, message.as_bytes());
+        base64::encode(result)
+    }
+}
 
-        debug!(
-            "[*] Debug:\nUrl:{}\nparameters:{:?}",
-            format!("{}{}", self.base_config.rest_domain, request_path),
-            parameters
-        );
+/// 公共和私有客户端共用的请求流程
+pub(crate) struct RestContext<'a> {
+    pub config: &'a OkxConfig,
+    pub transport: &'a dyn HttpTransport,
+    /// 为None时不签名
+    pub credentials: Option<Credentials<'a>>,
+    pub retry_policy: &'a RetryPolicy,
+    pub time_sync: &'a TimeSync,
+}
 
-        let res;
-        match client
-            .get(format!("{}{}", self.base_config.rest_domain, get_url_params))
-            .headers(headers)
-            .send()
-            .await {
-            Ok(resp) => {
-                match resp.text().await {
-                    Ok(val) => res = val,
-                    Err(err) => {
-                        return Err(err.into());
+impl RestContext<'_> {
+    pub async fn execute<T>(&self, request: RestRequest) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let mut attempt = 1;
+        loop {
+            let result = self.send_once(&request).await;
+
+            if request.idempotent {
+                let resp_err = match &result {
+                    Ok(resp) => response_error(resp),
+                    Err(_) => None,
+                };
+                if let Some(err) = result.as_ref().err().or(resp_err.as_ref()) {
+                    if self.retry_policy.should_retry(attempt, err) {
+                        let delay = self.retry_policy.delay(attempt);
+                        warn!("request {} failed, retry after {:?}. attempt:{} error:{}", request.path, delay, attempt, err);
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                        continue;
                     }
                 }
-            },
-            Err(err) => {
-                return Err(err.into());
             }
-        };
-
-        debug!("[*] Response {:#?}", res);
 
-        match serde_json::from_str::<T>(&res) {
-            Ok(val) => Ok(val),
-            Err(err) => Err(err.into()),
+            let resp = result?;
+            return match serde_json::from_str::<T>(&resp.body) {
+                Ok(val) => Ok(val),
+                Err(_) if !resp.status.is_success() => Err(OkxError::HttpError {
+                    status: resp.status.as_u16(),
+                    message: resp.body,
+                }),
+                Err(err) => Err(err.into()),
+            };
         }
     }
 
-    pub async fn post<T>(
-        &self,
-        request_path: &str,
-        parameters: impl Serialize+ Debug,
-    ) -> Result<T>
-    where
-        T: DeserializeOwned + std::fmt::Debug,
-    {
-        // # 获取本地时间
-        let timestamp = self.get_timestamp();
+    async fn send_once(&self, request: &RestRequest) -> Result<HttpResponse> {
+        let timestamp = self.time_sync.rest_timestamp();
+        let request_path = request.request_path();
+        let headers = self.create_header(&timestamp, request, &request_path)?;
+        let url = format!("{}{}", self.config.rest_domain, request_path);
+
+        debug!("[*] Debug:\nMethod:{}\nUrl:{}\nbody:{:?}", request.method, url, request.body);
 
-        let data = serde_json::to_string(&parameters).unwrap();
+        let resp = self
+            .transport
+            .send(HttpRequest {
+                method: request.method.clone(),
+                url,
+                headers,
+                body: request.body.clone(),
+            })
+            .await?;
 
-        let message = format!("{}POST{}{}", timestamp, request_path, &data);
+        debug!("[*] Response {} {:#?}", resp.status, resp.body);
 
-        let sign = self.sign(&message);
+        Ok(resp)
+    }
 
-        let headers = self.create_header(&sign, &timestamp);
+    fn create_header(&self, timestamp: &str, request: &RestRequest, request_path: &str) -> Result<HeaderMap> {
+        // 处理请求头 headers
+        let mut header_map = HeaderMap::new();
 
-        let client = reqwest::Client::new();
+        if let Some(credentials) = &self.credentials {
+            let message = format!(
+                "{}{}{}{}",
+                timestamp,
+                request.method.as_str(),
+                request_path,
+                request.body.as_deref().unwrap_or_default()
+            );
 
-        debug!("[*] Debug:parameters {:?}", parameters);
+            header_map.insert("OK-ACCESS-KEY", HeaderValue::from_str(credentials.api_key)?);
+            header_map.insert("OK-ACCESS-SIGN", HeaderValue::from_str(&credentials.sign(&message))?);
+            header_map.insert("OK-ACCESS-PASSPHRASE", HeaderValue::from_str(credentials.passphrase)?);
+        }
 
-        debug!(
-                "[*] Debug:\nUrl:{}\nparameters:{:?}",
-                format!("{}{}", self.base_config.rest_domain, request_path),
-                parameters
+        header_map.insert("OK-ACCESS-TIMESTAMP", HeaderValue::from_str(timestamp)?);
+        header_map.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/json; charset=UTF-8"),
         );
 
-        let res;
-        match client
-            .post(format!("{}{}", self.base_config.rest_domain, request_path))
-            .headers(headers)
-            .json(&parameters)
-            .send()
-            .await {
-            Ok(resp) => {
-                match resp.text().await {
-                    Ok(val) => res = val,
-                    Err(err) => {
-                        return Err(err.into());
-                    }
-                }
-            },
-            Err(err) => {
-                return Err(err.into());
-            }
-        };
+        // 如果是测试网
+        if self.config.testnet {
+            header_map.insert("x-simulated-trading", HeaderValue::from_static("1"));
+        }
 
-        debug!("[*] Response {:#?}", res);
+        Ok(header_map)
+    }
+}
 
-        match serde_json::from_str::<T>(&res) {
-            Ok(val) => Ok(val),
-            Err(err) => Err(err.into()),
+/// 从响应中提取错误，用于判断是否需要重试
+fn response_error(resp: &HttpResponse) -> Option<OkxError> {
+    #[derive(Deserialize)]
+    struct ResponseCode {
+        #[serde(deserialize_with = "from_str")]
+        code: i32,
+        #[serde(default)]
+        msg: String,
+    }
+
+    match serde_json::from_str::<ResponseCode>(&resp.body) {
+        Ok(val) if val.code != 0 => Some(OkxError::RemoteError {
+            code: OkxErrorCode::from_code(val.code),
+            message: val.msg,
+        }),
+        Ok(_) => None,
+        Err(_) if !resp.status.is_success() => Some(OkxError::HttpError {
+            status: resp.status.as_u16(),
+            message: resp.body.clone(),
+        }),
+        Err(_) => None,
+    }
+}
+
+impl OkxAccountClient {
+    pub(crate) fn rest_context(&self) -> RestContext<'_> {
+        RestContext {
+            config: &self.base_config,
+            transport: self.transport(),
+            credentials: Some(Credentials {
+                api_key: &self.api_key,
+                secret_key: &self.secret_key,
+                passphrase: &self.passphrase,
+            }),
+            retry_policy: self.retry_policy(),
+            time_sync: self.time_sync(),
         }
     }
 
-    pub async fn post_vec<T>(
+    /// 发送请求并解析返回结果
+    pub async fn send_request<T>(&self, request: RestRequest) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.rest_context().execute(request).await
+    }
+
+    pub async fn get<T>(
         &self,
         request_path: &str,
-        parameters: &Vec<BTreeMap<String, String>>,
+        parameters: &BTreeMap<String, String>,
     ) -> Result<T>
     where
         T: DeserializeOwned + std::fmt::Debug,
     {
-        // # 获取本地时间
-        let timestamp = self.get_timestamp();
-
-        let data = serde_json::to_string(parameters).unwrap();
-
-        let message = format!("{}POST{}{}", timestamp, request_path, &data);
-
-        let sign = self.sign(&message);
-        // println!("sign : {:?} ", sign);
-        let headers = self.create_header(&sign, &timestamp);
-
-        let client = reqwest::Client::new();
-
-        debug!(
-                "[*] Debug:\nUrl:{}\nparameters:{:?}",
-                format!("{}{}", self.base_config.rest_domain, request_path),
-                parameters
-            );
-
-        let res;
-        match client
-            .post(format!("{}{}", self.base_config.rest_domain, request_path))
-            .headers(headers)
-            .json(&parameters)
-            .send()
-            .await {
-            Ok(resp) => {
-                match resp.text().await {
-                    Ok(val) => res = val,
-                    Err(err) => {
-                        return Err(err.into());
-                    }
-                }
-            },
-            Err(err) => {
-                return Err(err.into());
-            }
-        };
-
-        debug!("[*] Response {:#?}", res);
-
-        match serde_json::from_str::<T>(&res) {
-            Ok(val) => Ok(val),
-            Err(err) => Err(err.into()),
-        }
+        self.send_request(RestRequest::get(request_path, parameters)).await
     }
 
-    fn create_header(&self, sign: &str, timestamp: &str) -> HeaderMap {
-        // 处理请求头 headers
-
-        let mut header_map = HeaderMap::new();
-
-        header_map.insert(
-            "OK-ACCESS-KEY",
-            HeaderValue::from_str(&self.api_key).unwrap(),
-        );
-        header_map.insert("OK-ACCESS-SIGN", HeaderValue::from_str(&sign).unwrap());
-        header_map.insert(
-            "OK-ACCESS-TIMESTAMP",
-            HeaderValue::from_str(&timestamp).unwrap(),
-        );
-        header_map.insert(
-            "OK-ACCESS-PASSPHRASE",
-            HeaderValue::from_str(&self.passphrase).unwrap(),
-        );
-        header_map.insert(
-            "CONTENT_TYPE",
-            HeaderValue::from_static("application/json; charset=UTF-8"),
-        );
-
-        // 如果是测试网
-        if self.base_config.testnet {
-            header_map.insert("x-simulated-trading", HeaderValue::from_static("1"));
-        }
-
-        header_map
+    pub async fn post<T>(
+        &self,
+        request_path: &str,
+        parameters: impl Serialize + Debug,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned + std::fmt::Debug,
+    {
+        self.send_request(RestRequest::post(request_path, &parameters)?).await
     }
 
-    fn parse_params_to_str(&self, parameters: &BTreeMap<String, String>) -> String {
-        parameters
-            .into_iter()
-            .map(|(key, value)| format!("{}={}", key, value))
-            .collect::<Vec<String>>()
-            .join("&")
-    }
-    // 做签名
-    fn sign(&self, message: &String) -> String {
-        let hmac_key = ring::hmac::Key::new(hmac::HMAC_SHA256, &self.secret_key.as_bytes());
-        let result = ring::hmac::sign(&hmac_key, &message.as_bytes());
-        base64::encode(result)
+    pub async fn post_vec<T>(
+        &self,
+        request_path: &str,
+        parameters: &Vec<BTreeMap<String, String>>,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned + std::fmt::Debug,
+    {
+        self.post(request_path, parameters).await
     }
 
     pub fn get_timestamp(&self) -> String {
-        chrono::Utc::now()
-            .format("%Y-%m-%dT%H:%M:%S%.3fZ")
-            .to_string()
+        self.time_sync().rest_timestamp()
     }
 }
 
 impl OkxPublicClient {
+    pub(crate) fn rest_context(&self) -> RestContext<'_> {
+        RestContext {
+            config: &self.base_config,
+            transport: self.transport(),
+            credentials: None,
+            retry_policy: self.retry_policy(),
+            time_sync: self.time_sync(),
+        }
+    }
+
+    /// 发送请求并解析返回结果
+    pub async fn send_request<T>(&self, request: RestRequest) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.rest_context().execute(request).await
+    }
+
     pub async fn get<T>(
         &self,
         request_path: &str,
@@ -247,113 +302,18 @@ impl OkxPublicClient {
         where
             T: DeserializeOwned + std::fmt::Debug,
     {
-        // # 获取本地时间
-        let timestamp = self.get_timestamp();
-
-        let mut get_url_params: String = String::from(request_path);
-
-        // 有数据就格式化参数
-        if parameters.len() != 0 {
-            get_url_params = format!("{}?{}", request_path, self.parse_params_to_str(parameters));
-        }
-        // println!("url {}",get_url_params);
-        // OK-ACCESS-SIGN的请求头是对timestamp + method + requestPath + body字符串(+表示字符串连接)，以及SecretKey
-
-        // let message = format!("{}GET{}{}", timestamp, get_url_params, &self.secret_key);
-        let _message = format!("{}GET{}", timestamp, get_url_params);
-
-        // println!("{:?}", message);
-
-        let headers = self.create_header(&timestamp);
-
-        let client = reqwest::Client::new();
-
-        debug!(
-            "[*] Debug:\nUrl:{}\nparameters:{:?}",
-            format!("{}{}", self.base_config.rest_domain, request_path),
-            parameters
-        );
-
-        let res;
-        match client
-            .get(format!("{}{}", self.base_config.rest_domain, get_url_params))
-            .headers(headers)
-            .send()
-            .await {
-            Ok(resp) => {
-                match resp.text().await {
-                    Ok(val) => res = val,
-                    Err(err) => {
-                        return Err(err.into());
-                    }
-                }
-            },
-            Err(err) => {
-                return Err(err.into());
-            }
-        };
-
-        debug!("[*] Response {:#?}", res);
-
-        match serde_json::from_str::<T>(&res) {
-            Ok(val) => Ok(val),
-            Err(err) => Err(err.into()),
-        }
+        self.send_request(RestRequest::get(request_path, parameters)).await
     }
 
     pub async fn post<T>(
         &self,
         request_path: &str,
-        parameters: impl Serialize+ Debug,
+        parameters: impl Serialize + Debug,
     ) -> Result<T>
         where
             T: DeserializeOwned + std::fmt::Debug,
     {
-        // # 获取本地时间
-        let timestamp = self.get_timestamp();
-
-        let data = serde_json::to_string(&parameters).unwrap();
-
-        let _message = format!("{}POST{}{}", timestamp, request_path, &data);
-
-        let headers = self.create_header( &timestamp);
-
-        let client = reqwest::Client::new();
-
-        debug!("[*] Debug:parameters {:?}", parameters);
-
-        debug!(
-                "[*] Debug:\nUrl:{}\nparameters:{:?}",
-                format!("{}{}", self.base_config.rest_domain, request_path),
-                parameters
-        );
-
-        let res;
-        match client
-            .post(format!("{}{}", self.base_config.rest_domain, request_path))
-            .headers(headers)
-            .json(&parameters)
-            .send()
-            .await {
-            Ok(resp) => {
-                match resp.text().await {
-                    Ok(val) => res = val,
-                    Err(err) => {
-                        return Err(err.into());
-                    }
-                }
-            },
-            Err(err) => {
-                return Err(err.into());
-            }
-        };
-
-        debug!("[*] Response {:#?}", res);
-
-        match serde_json::from_str::<T>(&res) {
-            Ok(val) => Ok(val),
-            Err(err) => Err(err.into()),
-        }
+        self.send_request(RestRequest::post(request_path, &parameters)?).await
     }
 
     pub async fn post_vec<T>(
@@ -364,85 +324,74 @@ impl OkxPublicClient {
         where
             T: DeserializeOwned + std::fmt::Debug,
     {
-        // # 获取本地时间
-        let timestamp = self.get_timestamp();
-
-        let data = serde_json::to_string(parameters).unwrap();
-
-        let _message = format!("{}POST{}{}", timestamp, request_path, &data);
-
-        // println!("sign : {:?} ", sign);
-        let headers = self.create_header( &timestamp);
-
-        let client = reqwest::Client::new();
-
-        debug!(
-                "[*] Debug:\nUrl:{}\nparameters:{:?}",
-                format!("{}{}", self.base_config.rest_domain, request_path),
-                parameters
-            );
-
-        let res;
-        match client
-            .post(format!("{}{}", self.base_config.rest_domain, request_path))
-            .headers(headers)
-            .json(&parameters)
-            .send()
-            .await {
-            Ok(resp) => {
-                match resp.text().await {
-                    Ok(val) => res = val,
-                    Err(err) => {
-                        return Err(err.into());
-                    }
-                }
-            },
-            Err(err) => {
-                return Err(err.into());
-            }
-        };
-
-        debug!("[*] Response {:#?}", res);
-
-        match serde_json::from_str::<T>(&res) {
-            Ok(val) => Ok(val),
-            Err(err) => Err(err.into()),
-        }
+        self.post(request_path, parameters).await
     }
 
-    fn create_header(&self, timestamp: &str) -> HeaderMap {
-        // 处理请求头 headers
-
-        let mut header_map = HeaderMap::new();
-
-        header_map.insert(
-            "OK-ACCESS-TIMESTAMP",
-            HeaderValue::from_str(&timestamp).unwrap(),
-        );
-        header_map.insert(
-            "CONTENT_TYPE",
-            HeaderValue::from_static("application/json; charset=UTF-8"),
-        );
-
-        // 如果是测试网
-        if self.base_config.testnet {
-            header_map.insert("x-simulated-trading", HeaderValue::from_static("1"));
-        }
+    pub fn get_timestamp(&self) -> String {
+        self.time_sync().rest_timestamp()
+    }
+}
 
-        header_map
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use crate::{MockTransport, OkxAccountClient, OkxError, RestApi};
+    use super::Credentials;
+
+    #[tokio::test]
+    pub async fn test_signed_post() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(r#"{"code":"0","msg":"","data":[]}"#);
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport.clone());
+
+        let params = vec![("instId", "BTC-USDT")].into_iter().collect::<std::collections::BTreeMap<_, _>>();
+        let result = client.post::<RestApi<serde_json::Value>>("/api/v5/trade/cancel-order", &params).await.unwrap();
+        assert!(result.is_success());
+
+        let request = &transport.requests()[0];
+        let body = r#"{"instId":"BTC-USDT"}"#;
+        assert_eq!(request.body.as_deref(), Some(body));
+
+        let timestamp = request.headers["OK-ACCESS-TIMESTAMP"].to_str().unwrap();
+        let credentials = Credentials { api_key: "key", secret_key: "secret", passphrase: "pass" };
+        let sign = credentials.sign(&format!("{}POST/api/v5/trade/cancel-order{}", timestamp, body));
+        assert_eq!(request.headers["OK-ACCESS-SIGN"], sign.as_str());
+        assert_eq!(request.headers["OK-ACCESS-KEY"], "key");
+        assert_eq!(request.headers["x-simulated-trading"], "1");
     }
 
-    fn parse_params_to_str(&self, parameters: &BTreeMap<String, String>) -> String {
-        parameters
-            .into_iter()
-            .map(|(key, value)| format!("{}={}", key, value))
-            .collect::<Vec<String>>()
-            .join("&")
+    #[tokio::test]
+    pub async fn test_http_error() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_response(crate::HttpResponse::new(http::StatusCode::BAD_GATEWAY, "<html>bad gateway</html>"));
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport);
+
+        let result = client.get::<RestApi<serde_json::Value>>("/api/v5/account/config", &Default::default()).await;
+        assert!(matches!(result, Err(OkxError::HttpError { status: 502, .. })));
     }
 
-    pub fn get_timestamp(&self) -> String {
-        chrono::Utc::now()
-            .format("%Y-%m-%dT%H:%M:%S%.3fZ")
-            .to_string()
+    #[tokio::test]
+    pub async fn test_retry_idempotent_request() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_response(crate::HttpResponse::new(http::StatusCode::TOO_MANY_REQUESTS, r#"{"code":"50011","msg":"Too Many Requests"}"#));
+        transport.push_error(OkxError::TransportError("connection reset".into()));
+        transport.push_json(r#"{"code":"0","msg":"","data":[]}"#);
+        let policy = crate::RetryPolicy {
+            base_delay: std::time::Duration::from_millis(1),
+            ..Default::default()
+        };
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport.clone())
+            .with_retry_policy(policy);
+
+        let result = client.get::<RestApi<serde_json::Value>>("/api/v5/account/balance", &Default::default()).await.unwrap();
+        assert!(result.is_success());
+        assert_eq!(transport.requests().len(), 3);
+
+        // POST请求默认不重试
+        transport.push_json(r#"{"code":"50011","msg":"Too Many Requests","data":[]}"#);
+        transport.push_json(r#"{"code":"0","msg":"","data":[]}"#);
+        let result = client.post::<RestApi<serde_json::Value>>("/api/v5/trade/order", &serde_json::json!({})).await.unwrap();
+        assert!(matches!(result.to_result(), Err(OkxError::RemoteError { code: crate::OkxErrorCode::RateLimitReached, .. })));
+        assert_eq!(transport.pending(), 1);
     }
-}
\ No newline at end of file
+}
@@ -1,8 +1,10 @@
 use std::collections::BTreeMap;
+use futures_util::Stream;
 use serde::{Deserialize, Serialize};
 use crate::api_enum::APiEnum;
 use crate::apikey::OkxAccountClient;
 use super::models::*;
+use super::sign::RestRequest;
 use crate::okx_error::*;
 
 #[derive(Serialize, Debug, Deserialize)]
@@ -21,6 +23,51 @@ pub struct OrdersPendingFilter {
     pub before: Option<String>,
     pub limit: Option<String>,
 }
+
+/// 成交明细的查询条件
+/// after、before为账单ID(billId)，begin、end为Unix时间戳的毫秒数
+#[derive(Serialize, Debug, Deserialize, Default, Clone)]
+pub struct FillsFilter {
+    /// 查询成交明细（三个月）时必填
+    #[serde(rename="instType")]
+    pub inst_type: Option<InstType>,
+    pub uly: Option<String>,
+    #[serde(rename="instFamily")]
+    pub inst_family: Option<String>,
+    #[serde(rename="instId")]
+    pub inst_id: Option<String>,
+    #[serde(rename="ordId")]
+    pub ord_id: Option<String>,
+    /// 请求此ID之前（更旧的数据）的分页内容
+    pub after: Option<String>,
+    /// 请求此ID之后（更新的数据）的分页内容
+    pub before: Option<String>,
+    pub begin: Option<i64>,
+    pub end: Option<i64>,
+    /// 返回结果的数量，最大为100，默认100条
+    pub limit: Option<usize>,
+}
+
+/// 策略委托单列表的查询条件
+/// after、before为策略委托单ID(algoId)
+#[derive(Serialize, Debug, Deserialize, Default, Clone)]
+pub struct AlgoOrdersFilter {
+    #[serde(rename="algoId")]
+    pub algo_id: Option<String>,
+    #[serde(rename="algoClOrdId")]
+    pub algo_cl_ord_id: Option<String>,
+    /// 订单状态，仅适用于历史策略委托单列表，与algoId必须传一个
+    pub state: Option<AlgoOrderState>,
+    #[serde(rename="instType")]
+    pub inst_type: Option<InstType>,
+    #[serde(rename="instId")]
+    pub inst_id: Option<String>,
+    pub after: Option<String>,
+    pub before: Option<String>,
+    /// 返回结果的数量，最大为100，默认100条
+    pub limit: Option<usize>,
+}
+
 impl OkxAccountClient {
     // 获取未成交订单列表
     // 获取当前账户下所有未成交订单信息
@@ -31,7 +78,7 @@ impl OkxAccountClient {
     ) -> Result<Vec<TradeOrdersPending>>
     {
         //  /api/v5/trade/orders-pending
-        self.limit_mgr().check_limit(APiEnum::TradeOrdersPending as u32, 1, 60, 2)?;
+        self.limit_mgr().check_limit(APiEnum::TradeOrdersPending, 1).await?;
         let mut params: BTreeMap<String, String> = BTreeMap::new();
 
         if let Some(inst_type) = &filter.inst_type {
@@ -91,7 +138,7 @@ impl OkxAccountClient {
     ) -> Result<Vec<TradeOrdersHistory>>
     {
         //  /api/index/v3/BTC-USD/constituents
-        self.limit_mgr().check_limit(APiEnum::TradeOrdersHistory as u32, 1, 40, 2)?;
+        self.limit_mgr().check_limit(APiEnum::TradeOrdersHistory, 1).await?;
         let mut params: BTreeMap<String, String> = BTreeMap::new();
 
         params.insert("instType".into(), inst_type.into());
@@ -149,10 +196,10 @@ impl OkxAccountClient {
         inst_id: impl Into<String>,
         order_ids: &[String],
         cl_ord_id: &[String],
-    ) -> Result<Vec<TradeCancelBatchOrders>>
+    ) -> Result<Vec<Result<TradeCancelBatchOrders>>>
     {
         let inst_id = inst_id.into();
-        self.limit_mgr().check_limit_with_inst_id(APiEnum::TradeCancelBatchOrders as u32,  &inst_id, (order_ids.len() + cl_ord_id.len()) as u32, 300, 2)?;
+        self.check_instrument_limit(APiEnum::TradeCancelBatchOrders, &inst_id, (order_ids.len() + cl_ord_id.len()) as u32).await?;
         let mut params_vec = Vec::new();
         for item in order_ids {
             let mut params: BTreeMap<String, String> = BTreeMap::new();
@@ -174,21 +221,30 @@ impl OkxAccountClient {
                 "/api/v5/trade/cancel-batch-orders",
                 &params_vec,
             )
-            .await?.to_result()
+            .await?.to_sub_results()
     }
 
     /// 下单接口
+    /// 发送前会检查字段组合，设置了InstrumentRegistry时还会检查产品的精度和数量限制，不符合要求时返回InvalidOrder错误
+    /// 设置了重试策略且订单带有clOrdId时，失败后会自动重试
     /// 限速：60次/2s
     /// 跟单交易带单合约的限速：1次/2s
     /// 限速规则（期权以外）：UserID + Instrument ID
     /// 限速规则（只限期权）：UserID + Instrument Family
     pub async fn trade_order(&self, order_obj: OrderRequestInfo) -> Result<TradeOrder>
     {
-        self.limit_mgr().check_limit_with_inst_id(APiEnum::TradePlaceOrder as u32,  &order_obj.inst_id, 1, 60, 2)?;
+        order_obj.validate()?;
+        if let Some(registry) = self.instrument_registry() {
+            registry.check_order(&order_obj)?;
+        }
+        self.check_instrument_limit(APiEnum::TradePlaceOrder, &order_obj.inst_id, 1).await?;
 
+        // 设置了clOrdId时，重复的请求会被okx拒绝，可以安全地重试
+        let request = RestRequest::post("/api/v5/trade/order", &order_obj)?
+            .idempotent(order_obj.cl_ord_id.is_some());
         self
-            .post::<RestApi<TradeOrder>>("/api/v5/trade/order", &order_obj)
-            .await?.to_result_one()
+            .send_request::<RestApi<TradeOrder>>(request)
+            .await?.to_sub_result_one()
     }
 
     /// 下单接口
@@ -197,25 +253,31 @@ impl OkxAccountClient {
     /// 限速规则（期权以外）：UserID + Instrument ID
     /// 限速规则（只限期权）：UserID + Instrument Family
     /// 与其他限速按接口调用次数不同，该接口限速按订单的总个数限速。如果单次批量请求中只有一个元素，则算在单个`下单`限速中。
-    pub async fn trade_batch_order(&self, inst_id: &str, order_obj: Vec<OrderRequestInfo>) -> Result<Vec<TradeOrder>>
+    pub async fn trade_batch_order(&self, inst_id: &str, order_obj: Vec<OrderRequestInfo>) -> Result<Vec<Result<TradeOrder>>>
     {
         if order_obj.len() > 20 {
             return Err(OkxError::OutOfMaxOrderSize);
         }
         for item in &order_obj {
-            if item.inst_id != item.inst_id {
+            if item.inst_id != inst_id {
                 return Err(OkxError::MustHaveSameInstId);
             }
+            item.validate()?;
+            if let Some(registry) = self.instrument_registry() {
+                registry.check_order(item)?;
+            }
         }
         if order_obj.len() == 1 {
-            self.limit_mgr().check_limit_with_inst_id(APiEnum::TradePlaceOrder as u32,  &inst_id, 1, 60, 2)?;
+            self.check_instrument_limit(APiEnum::TradePlaceOrder, inst_id, 1).await?;
         } else {
-            self.limit_mgr().check_limit_with_inst_id(APiEnum::TradePlaceBatchOrders as u32,  &inst_id, order_obj.len() as u32, 300, 2)?;
+            self.check_instrument_limit(APiEnum::TradePlaceBatchOrders, inst_id, order_obj.len() as u32).await?;
         }
 
+        let request = RestRequest::post("/api/v5/trade/batch-orders", &order_obj)?
+            .idempotent(order_obj.iter().all(|item| item.cl_ord_id.is_some()));
         self
-            .post::<RestApi<TradeOrder>>("/api/v5/trade/batch-orders", &order_obj)
-            .await?.to_result()
+            .send_request::<RestApi<TradeOrder>>(request)
+            .await?.to_sub_results()
     }
 
     ///  获取订单信息
@@ -234,7 +296,7 @@ impl OkxAccountClient {
         T: Into<String>,
     {
         let inst_id = inst_id.into();
-        self.limit_mgr().check_limit_with_inst_id(APiEnum::TradeGetOrder as u32,  &inst_id, 1, 60, 2)?;
+        self.check_instrument_limit(APiEnum::TradeGetOrder, &inst_id, 1).await?;
         let mut params: BTreeMap<String, String> = BTreeMap::new();
 
         params.insert("instId".into(), inst_id);
@@ -252,10 +314,11 @@ impl OkxAccountClient {
             .await?.to_result_one_opt()
     }
 
-    ///     修改订单
+    /// 修改订单
     /// 修改当前未成交的挂单
     /// 限速：60次/2s
-    /// 限速规则：UserID + Instrument ID
+    /// 限速规则（期权以外）：UserID + Instrument ID
+    /// 限速规则（只限期权）：UserID + Instrument Family
     /// POST /api/v5/trade/amend-order
 
     pub async fn trade_amend_order<T>(
@@ -275,7 +338,7 @@ impl OkxAccountClient {
     {
         // let mut params_vec = Vec::new();
         let inst_id = inst_id.into();
-        self.limit_mgr().check_limit_with_inst_id(APiEnum::TradeAmendOrder as u32,  &inst_id, 1, 60, 2)?;
+        self.check_instrument_limit(APiEnum::TradeAmendOrder, &inst_id, 1).await?;
         let mut params: BTreeMap<String, String> = BTreeMap::new();
 
         params.insert("instId".into(), inst_id);
@@ -306,6 +369,526 @@ impl OkxAccountClient {
 
         self
             .post::<RestApi<TradeAmendOrder>>("/api/v5/trade/amend-order", &params)
+            .await?.to_sub_result_one()
+    }
+
+    /// 撤单
+    /// 撤销之前下的未完成订单
+    /// 限速：60次/2s
+    /// 限速规则（期权以外）：UserID + Instrument ID
+    /// 限速规则（只限期权）：UserID + Instrument Family
+    /// POST /api/v5/trade/cancel-order
+    pub async fn trade_cancel_order(&self, request: CancelOrderRequest) -> Result<TradeCancelOrder>
+    {
+        request.validate()?;
+        self.check_instrument_limit(APiEnum::TradeCancelOrder, &request.inst_id, 1).await?;
+
+        self
+            .post::<RestApi<TradeCancelOrder>>("/api/v5/trade/cancel-order", &request)
+            .await?.to_sub_result_one()
+    }
+
+    /// 批量修改订单
+    /// 修改未完成的订单，一次最多可批量修改20个订单
+    /// 限速：300个/2s
+    /// 限速规则（期权以外）：UserID + Instrument ID
+    /// 限速规则（只限期权）：UserID + Instrument Family
+    /// 与其他限速按接口调用次数不同，该接口限速按订单的总个数限速。如果单次批量请求中只有一个元素，则算在单个`修改订单`限速中。
+    /// POST /api/v5/trade/amend-batch-orders
+    pub async fn trade_amend_batch_orders(&self, inst_id: &str, requests: Vec<AmendOrderRequest>) -> Result<Vec<Result<TradeAmendBatchOrders>>>
+    {
+        if requests.len() > 20 {
+            return Err(OkxError::OutOfMaxOrderSize);
+        }
+        for item in &requests {
+            if item.inst_id != inst_id {
+                return Err(OkxError::MustHaveSameInstId);
+            }
+            item.validate()?;
+        }
+        if requests.len() == 1 {
+            self.check_instrument_limit(APiEnum::TradeAmendOrder, inst_id, 1).await?;
+        } else {
+            self.check_instrument_limit(APiEnum::TradeAmendBatchOrders, inst_id, requests.len() as u32).await?;
+        }
+
+        self
+            .post::<RestApi<TradeAmendBatchOrders>>("/api/v5/trade/amend-batch-orders", &requests)
+            .await?.to_sub_results()
+    }
+
+    /// 获取成交明细（近三天）
+    /// 限速：60次/2s
+    /// 限速规则：UserID
+    /// GET /api/v5/trade/fills
+    pub async fn trade_fills(&self, filter: FillsFilter) -> Result<Vec<Fill>>
+    {
+        self.limit_mgr().check_limit(APiEnum::TradeFills, 1).await?;
+        self.get_fills("/api/v5/trade/fills", &filter).await
+    }
+
+    /// 获取成交明细（近三个月），instType必填
+    /// 限速：10次/2s
+    /// 限速规则：UserID
+    /// GET /api/v5/trade/fills-history
+    pub async fn trade_fills_history(&self, filter: FillsFilter) -> Result<Vec<Fill>>
+    {
+        self.limit_mgr().check_limit(APiEnum::TradeFillsHistory, 1).await?;
+        self.get_fills("/api/v5/trade/fills-history", &filter).await
+    }
+
+    /// 按billId向前翻页，依次返回filter条件下的全部成交明细，顺序为从新到旧
+    /// history为true时查询近三个月的数据，否则查询近三天的数据
+    /// 每页请求前会等待限速额度，请求失败时返回错误并结束
+    pub fn trade_fills_stream(&self, filter: FillsFilter, history: bool) -> impl Stream<Item = Result<Fill>> + '_
+    {
+        let (api, path) = match history {
+            true => (APiEnum::TradeFillsHistory, "/api/v5/trade/fills-history"),
+            false => (APiEnum::TradeFills, "/api/v5/trade/fills"),
+        };
+        let page_size = filter.limit.unwrap_or(100);
+
+        self.bill_id_stream(api, page_size, filter.after.clone(), |item: &Fill| &item.bill_id, move |after| {
+            let filter = FillsFilter { after, ..filter.clone() };
+            async move { self.get_fills(path, &filter).await }
+        })
+    }
+
+    /// 策略委托下单
+    /// 设置了重试策略且订单带有algoClOrdId时，失败后会自动重试
+    /// 限速：20次/2s
+    /// 限速规则（期权以外）：UserID + Instrument ID
+    /// 限速规则（只限期权）：UserID + Instrument Family
+    /// POST /api/v5/trade/order-algo
+    pub async fn trade_order_algo(&self, request: AlgoOrderRequest) -> Result<TradeOrderAlgo>
+    {
+        request.validate()?;
+        let base = request.base();
+        self.check_instrument_limit(APiEnum::TradeOrderAlgo, &base.inst_id, 1).await?;
+
+        let request = RestRequest::post("/api/v5/trade/order-algo", &request)?
+            .idempotent(base.algo_cl_ord_id.is_some());
+        self
+            .send_request::<RestApi<TradeOrderAlgo>>(request)
+            .await?.to_sub_result_one()
+    }
+
+    /// 撤销策略委托订单，每次最多可以撤销10个策略委托单，可以是不同产品的订单
+    /// 限速：20次/2s
+    /// 限速规则：UserID
+    /// POST /api/v5/trade/cancel-algos
+    pub async fn trade_cancel_algos(&self, requests: Vec<CancelAlgoRequest>) -> Result<Vec<Result<TradeCancelAlgos>>>
+    {
+        if requests.is_empty() {
+            return Err(OkxError::InvalidOrder("no algo order to cancel".to_string()));
+        }
+        if requests.len() > 10 {
+            return Err(OkxError::OutOfMaxOrderSize);
+        }
+        self.limit_mgr().check_limit(APiEnum::TradeCancelAlgos, 1).await?;
+
+        self
+            .post::<RestApi<TradeCancelAlgos>>("/api/v5/trade/cancel-algos", &requests)
+            .await?.to_sub_results()
+    }
+
+    /// 修改策略委托订单，仅支持止盈止损和计划委托订单
+    /// 限速：20次/2s
+    /// 限速规则（期权以外）：UserID + Instrument ID
+    /// 限速规则（只限期权）：UserID + Instrument Family
+    /// POST /api/v5/trade/amend-algos
+    pub async fn trade_amend_algos(&self, request: AmendAlgoRequest) -> Result<TradeAmendAlgos>
+    {
+        request.validate()?;
+        self.check_instrument_limit(APiEnum::TradeAmendAlgos, &request.inst_id, 1).await?;
+
+        self
+            .post::<RestApi<TradeAmendAlgos>>("/api/v5/trade/amend-algos", &request)
+            .await?.to_sub_result_one()
+    }
+
+    /// 获取未完成策略委托单列表，ord_type支持同时查询conditional和oco
+    /// 限速：20次/2s
+    /// 限速规则：UserID
+    /// GET /api/v5/trade/orders-algo-pending
+    pub async fn trade_orders_algo_pending(&self, ord_type: &[AlgoOrderType], filter: AlgoOrdersFilter) -> Result<Vec<AlgoOrder>>
+    {
+        self.limit_mgr().check_limit(APiEnum::TradeOrdersAlgoPending, 1).await?;
+        self.get_algo_orders("/api/v5/trade/orders-algo-pending", ord_type, filter).await
+    }
+
+    /// 获取最近3个月当前账户下所有策略委托单列表，filter中的state和algoId必须传一个
+    /// 限速：20次/2s
+    /// 限速规则：UserID
+    /// GET /api/v5/trade/orders-algo-history
+    pub async fn trade_orders_algo_history(&self, ord_type: &[AlgoOrderType], filter: AlgoOrdersFilter) -> Result<Vec<AlgoOrder>>
+    {
+        self.limit_mgr().check_limit(APiEnum::TradeOrdersAlgoHistory, 1).await?;
+        self.get_algo_orders("/api/v5/trade/orders-algo-history", ord_type, filter).await
+    }
+
+    /// 获取策略委托单信息，algo_id和algo_cl_ord_id必须传一个
+    /// 限速：20次/2s
+    /// 限速规则：UserID
+    /// GET /api/v5/trade/order-algo
+    pub async fn get_algo_order<T>(&self, algo_id: Option<T>, algo_cl_ord_id: Option<T>) -> Result<Option<AlgoOrder>>
+    where
+        T: Into<String>,
+    {
+        self.limit_mgr().check_limit(APiEnum::TradeGetAlgoOrder, 1).await?;
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(algo_id) = algo_id {
+            params.insert("algoId".into(), algo_id.into());
+        }
+        if let Some(algo_cl_ord_id) = algo_cl_ord_id {
+            params.insert("algoClOrdId".into(), algo_cl_ord_id.into());
+        }
+
+        self
+            .get::<RestApi<AlgoOrder>>("/api/v5/trade/order-algo", &params)
+            .await?.to_result_one_opt()
+    }
+
+    async fn get_algo_orders(&self, path: &str, ord_type: &[AlgoOrderType], filter: AlgoOrdersFilter) -> Result<Vec<AlgoOrder>>
+    {
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        let val: Vec<String> = ord_type.iter().map(|item| -> String { (*item).into() }).collect();
+        params.insert("ordType".into(), val.join(","));
+        if let Some(algo_id) = filter.algo_id {
+            params.insert("algoId".into(), algo_id);
+        }
+        if let Some(algo_cl_ord_id) = filter.algo_cl_ord_id {
+            params.insert("algoClOrdId".into(), algo_cl_ord_id);
+        }
+        if let Some(state) = filter.state {
+            params.insert("state".into(), state.into());
+        }
+        if let Some(inst_type) = filter.inst_type {
+            params.insert("instType".into(), inst_type.into());
+        }
+        if let Some(inst_id) = filter.inst_id {
+            params.insert("instId".into(), inst_id);
+        }
+        if let Some(after) = filter.after {
+            params.insert("after".into(), after);
+        }
+        if let Some(before) = filter.before {
+            params.insert("before".into(), before);
+        }
+        if let Some(limit) = filter.limit {
+            params.insert("limit".into(), limit.to_string());
+        }
+
+        self
+            .get::<RestApi<AlgoOrder>>(path, &params)
+            .await?.to_result()
+    }
+
+    async fn get_fills(&self, path: &str, filter: &FillsFilter) -> Result<Vec<Fill>>
+    {
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+
+        if let Some(inst_type) = filter.inst_type {
+            params.insert("instType".into(), inst_type.into());
+        }
+        if let Some(uly) = &filter.uly {
+            params.insert("uly".into(), uly.into());
+        }
+        if let Some(inst_family) = &filter.inst_family {
+            params.insert("instFamily".into(), inst_family.into());
+        }
+        if let Some(inst_id) = &filter.inst_id {
+            params.insert("instId".into(), inst_id.into());
+        }
+        if let Some(ord_id) = &filter.ord_id {
+            params.insert("ordId".into(), ord_id.into());
+        }
+        if let Some(after) = &filter.after {
+            params.insert("after".into(), after.into());
+        }
+        if let Some(before) = &filter.before {
+            params.insert("before".into(), before.into());
+        }
+        if let Some(begin) = filter.begin {
+            params.insert("begin".into(), begin.to_string());
+        }
+        if let Some(end) = filter.end {
+            params.insert("end".into(), end.to_string());
+        }
+        if let Some(limit) = filter.limit {
+            params.insert("limit".into(), limit.to_string());
+        }
+
+        self
+            .get::<RestApi<Fill>>(path, &params)
+            .await?.to_result()
+    }
+
+    /// 市价仓位全平
+    /// 限速：20次/2s
+    /// 限速规则（期权以外）：UserID + Instrument ID
+    /// 限速规则（只限期权）：UserID + Instrument Family
+    /// POST /api/v5/trade/close-position
+    pub async fn trade_close_position(&self, request: ClosePositionRequest) -> Result<TradeClosePosition>
+    {
+        self.check_instrument_limit(APiEnum::TradeClosePosition, &request.inst_id, 1).await?;
+
+        self
+            .post::<RestApi<TradeClosePosition>>("/api/v5/trade/close-position", &request)
             .await?.to_result_one()
     }
+
+    /// 市价全平仓位，买卖模式下pos_side传None
+    /// auto_cxl为true时，会自动撤销该仓位的平仓挂单，否则存在平仓挂单时会失败
+    pub async fn close_position(
+        &self,
+        inst_id: impl Into<String>,
+        mgn_mode: TradeMode,
+        pos_side: Option<PositionSide>,
+        auto_cxl: bool,
+    ) -> Result<TradeClosePosition>
+    {
+        self.trade_close_position(ClosePositionRequest {
+            inst_id: inst_id.into(),
+            pos_side,
+            mgn_mode,
+            ccy: None,
+            auto_cxl: Some(auto_cxl),
+            cl_ord_id: None,
+            tag: None,
+        }).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use rust_decimal::Decimal;
+    use futures_util::StreamExt;
+    use crate::{AlgoMoveOrderStop, AlgoOrderBase, AlgoOrderRequest, AlgoOrderState, AlgoOrderType, AlgoOrdersFilter, AlgoTrigger, AmendAlgoRequest, AmendOrderRequest, CancelAlgoRequest, CancelOrderRequest, ExecuteType, Fill, FillsFilter, InstType, MockTransport, OkxAccountClient, OkxError, OkxErrorCode, OrderRequestInfo, OrderState, PositionSide, TpTriggerPxType, TradeMode, TradeSide};
+
+    #[tokio::test]
+    pub async fn test_batch_order_partial_success() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(r#"{"code":"2","msg":"Bulk operation partially succeeded.","data":[
+            {"clOrdId":"a1","ordId":"12345","tag":"","sCode":"0","sMsg":""},
+            {"clOrdId":"a2","ordId":"","tag":"","sCode":"51008","sMsg":"Order failed. Insufficient USDT balance in account."}]}"#);
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport);
+
+        let orders = vec![
+            OrderRequestInfo::new_spot_limit_order("BTC-USDT", TradeSide::Buy, Decimal::new(1, 2), Decimal::from(20000), Some("a1".into()), None),
+            OrderRequestInfo::new_spot_limit_order("BTC-USDT", TradeSide::Buy, Decimal::from(100), Decimal::from(20000), Some("a2".into()), None),
+        ];
+        let result = client.trade_batch_order("BTC-USDT", orders).await.unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].as_ref().unwrap().ord_id, "12345");
+
+        let err = result[1].as_ref().unwrap_err();
+        assert!(matches!(err, OkxError::RemoteError { code: OkxErrorCode::InsufficientBalance, .. }));
+        assert!(err.is_insufficient_funds());
+        assert!(!err.is_retryable());
+    }
+
+    #[tokio::test]
+    pub async fn test_get_trade_order() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"instType":"FUTURES","instId":"BTC-USD-200329","ccy":"","ordId":"312269865356374016",
+            "clOrdId":"b1","tag":"","px":"999","sz":"3","pnl":"5","ordType":"limit","side":"buy","posSide":"long","tdMode":"isolated",
+            "accFillSz":"0","fillPx":"","tradeId":"","fillSz":"","fillTime":"","avgPx":"","state":"live","lever":"20",
+            "attachAlgoClOrdId":"","tpTriggerPx":"","tpTriggerPxType":"","tpOrdPx":"","slTriggerPx":"","slTriggerPxType":"","slOrdPx":"",
+            "stpId":"","stpMode":"","feeCcy":"","fee":"","rebateCcy":"","rebate":"","source":"","category":"normal","reduceOnly":"false",
+            "cancelSource":"","cancelSourceReason":"","quickMgnType":"","algoClOrdId":"","algoId":"","tgtCcy":"",
+            "uTime":"1597026383085","cTime":"1597026383085"}]}"#);
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport);
+
+        let order = client.get_trade_order("BTC-USD-200329", Some("312269865356374016"), None).await.unwrap().unwrap();
+        assert_eq!(order.inst_type, InstType::Futures);
+        assert_eq!(order.state, OrderState::Live);
+        assert_eq!(order.pos_side, PositionSide::Long);
+        assert_eq!(order.td_mode, TradeMode::Isolated);
+        assert_eq!(order.px, Some(Decimal::from(999)));
+        assert_eq!(order.avg_px, None);
+        assert_eq!(order.lever, Some(Decimal::from(20)));
+        assert_eq!(order.fee, Decimal::ZERO);
+        assert!(!order.reduce_only);
+    }
+
+    #[tokio::test]
+    pub async fn test_cancel_and_amend_orders() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(r#"{"code":"1","msg":"","data":[{"clOrdId":"","ordId":"2510789768709120","sCode":"51400","sMsg":"Cancellation failed as the order does not exist."}]}"#);
+        transport.push_json(r#"{"code":"2","msg":"","data":[
+            {"clOrdId":"","ordId":"1","reqId":"r1","sCode":"0","sMsg":""},
+            {"clOrdId":"","ordId":"2","reqId":"r2","sCode":"51503","sMsg":"Order modification failed as the order does not exist."}]}"#);
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"clOrdId":"","instId":"BTC-USDT-SWAP","posSide":"long","tag":""}]}"#);
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport.clone());
+
+        let err = client.trade_cancel_order(CancelOrderRequest::with_ord_id("BTC-USDT", "2510789768709120")).await.unwrap_err();
+        assert_eq!(err.error_code(), Some(OkxErrorCode::CancelFailed));
+
+        let amend = |ord_id: &str, req_id: &str| AmendOrderRequest {
+            inst_id: "BTC-USDT".into(),
+            cxl_on_fail: None,
+            ord_id: Some(ord_id.into()),
+            cl_ord_id: None,
+            req_id: Some(req_id.into()),
+            new_sz: Some(Decimal::new(2, 1)),
+            new_px: None,
+        };
+        let result = client.trade_amend_batch_orders("BTC-USDT", vec![amend("1", "r1"), amend("2", "r2")]).await.unwrap();
+        assert_eq!(result[0].as_ref().unwrap().req_id, "r1");
+        assert!(result[1].is_err());
+        assert_eq!(transport.requests()[1].body.as_deref(),
+            Some(r#"[{"instId":"BTC-USDT","ordId":"1","reqId":"r1","newSz":"0.2"},{"instId":"BTC-USDT","ordId":"2","reqId":"r2","newSz":"0.2"}]"#));
+
+        let result = client.close_position("BTC-USDT-SWAP", TradeMode::Cross, Some(PositionSide::Long), true).await.unwrap();
+        assert_eq!(result.pos_side, PositionSide::Long);
+        assert_eq!(transport.requests()[2].body.as_deref(),
+            Some(r#"{"instId":"BTC-USDT-SWAP","posSide":"long","mgnMode":"cross","autoCxl":true}"#));
+
+        // 本地检查不通过时不发送请求
+        let cancel = CancelOrderRequest { inst_id: "BTC-USDT".into(), ord_id: None, cl_ord_id: None };
+        assert!(matches!(client.trade_cancel_order(cancel).await, Err(OkxError::InvalidOrder(_))));
+        let mut no_change = amend("1", "r1");
+        no_change.new_sz = None;
+        let result = client.trade_amend_batch_orders("BTC-USDT", vec![amend("2", "r2"), no_change]).await;
+        assert!(matches!(result, Err(OkxError::InvalidOrder(_))));
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    pub async fn test_fills_stream() {
+        let fill = |bill_id: &str, ts: i64| format!(r#"{{"instType":"SPOT","instId":"BTC-USDT","tradeId":"t{bill_id}","ordId":"o{bill_id}","clOrdId":"",
+            "billId":"{bill_id}","tag":"","fillPx":"20000","fillSz":"0.01","fillIdxPx":"","fillPnl":"0","fillMarkPx":"","side":"buy","posSide":"net",
+            "execType":"M","feeCcy":"BTC","fee":"-0.00001","ts":"{ts}","fillTime":"{ts}"}}"#);
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(format!(r#"{{"code":"0","msg":"","data":[{},{}]}}"#, fill("3", 3000), fill("2", 2000)));
+        transport.push_json(format!(r#"{{"code":"0","msg":"","data":[{}]}}"#, fill("1", 1000)));
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport.clone());
+
+        let filter = FillsFilter {
+            inst_type: Some(InstType::Spot),
+            begin: Some(1000),
+            end: Some(3000),
+            limit: Some(2),
+            ..Default::default()
+        };
+        let fills: Vec<Fill> = client.trade_fills_stream(filter, true).map(|item| item.unwrap()).collect().await;
+        assert_eq!(fills.iter().map(|item| item.bill_id.as_str()).collect::<Vec<_>>(), vec!["3", "2", "1"]);
+        assert_eq!(fills[0].exec_type, ExecuteType::Maker);
+        assert_eq!(fills[0].fee, Decimal::new(-1, 5));
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].url.contains("after=2"));
+    }
+
+    #[tokio::test]
+    pub async fn test_algo_order() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"algoId":"12345689","clOrdId":"","algoClOrdId":"a1","sCode":"0","sMsg":"","tag":""}]}"#);
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"activePx":"","actualPx":"","actualSide":"","actualSz":"0","algoClOrdId":"a1","algoId":"12345689",
+            "amendPxOnTriggerType":"","attachAlgoOrds":[],"cTime":"1724751378980","callbackRatio":"","callbackSpread":"","ccy":"","clOrdId":"",
+            "closeFraction":"","failCode":"0","instId":"BTC-USDT","instType":"SPOT","last":"62916.5","lever":"","moveTriggerPx":"","ordId":"",
+            "ordIdList":[],"ordPx":"-1","ordType":"trigger","posSide":"net","pxLimit":"","pxSpread":"","pxVar":"","quickMgnType":"","reduceOnly":"false",
+            "side":"buy","slOrdPx":"","slTriggerPx":"","slTriggerPxType":"","state":"live","sz":"10","szLimit":"","tag":"","tdMode":"cash","tgtCcy":"",
+            "timeInterval":"","tpOrdPx":"","tpTriggerPx":"","tpTriggerPxType":"","triggerPx":"60000","triggerPxType":"last","triggerTime":"","uTime":"1724751378980"}]}"#);
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport.clone());
+
+        let request_base = AlgoOrderBase {
+            inst_id: "BTC-USDT".into(),
+            td_mode: TradeMode::Cash,
+            ccy: None,
+            side: TradeSide::Buy,
+            pos_side: None,
+            sz: Decimal::from(10),
+            tag: None,
+            tgt_ccy: None,
+            algo_cl_ord_id: Some("a1".into()),
+            reduce_only: None,
+        };
+        let request = AlgoOrderRequest::Trigger(AlgoTrigger {
+            base: request_base.clone(),
+            trigger_px: Decimal::from(60000),
+            order_px: Decimal::from(-1),
+            trigger_px_type: Some(TpTriggerPxType::Last),
+        });
+        let result = client.trade_order_algo(request).await.unwrap();
+        assert_eq!(result.algo_id, "12345689");
+        assert_eq!(transport.requests()[0].body.as_deref(),
+            Some(r#"{"ordType":"trigger","instId":"BTC-USDT","tdMode":"cash","side":"buy","sz":"10","algoClOrdId":"a1","triggerPx":"60000","orderPx":"-1","triggerPxType":"last"}"#));
+
+        let orders = client.trade_orders_algo_pending(&[AlgoOrderType::Trigger], AlgoOrdersFilter::default()).await.unwrap();
+        assert_eq!(orders[0].ord_type, AlgoOrderType::Trigger);
+        assert_eq!(orders[0].state, AlgoOrderState::Live);
+        assert_eq!(orders[0].trigger_px, Some(Decimal::from(60000)));
+        assert_eq!(orders[0].ord_px, Some(Decimal::from(-1)));
+        assert_eq!(transport.requests()[1].url, "https://www.okx.com/api/v5/trade/orders-algo-pending?ordType=trigger");
+
+        transport.push_json(r#"{"code":"0","msg":"","data":[{"algoId":"12345689","sCode":"0","sMsg":""},{"algoId":"12345690","sCode":"0","sMsg":""}]}"#);
+        let requests = vec![CancelAlgoRequest::new("BTC-USDT", "12345689"), CancelAlgoRequest::new("ETH-USDT-SWAP", "12345690")];
+        let result = client.trade_cancel_algos(requests).await.unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[1].as_ref().unwrap().algo_id, "12345690");
+        assert_eq!(transport.requests()[2].body.as_deref(),
+            Some(r#"[{"algoId":"12345689","instId":"BTC-USDT"},{"algoId":"12345690","instId":"ETH-USDT-SWAP"}]"#));
+
+        let amend = AmendAlgoRequest { inst_id: "BTC-USDT".into(), new_sz: Some(Decimal::ONE), ..Default::default() };
+        assert!(matches!(client.trade_amend_algos(amend).await, Err(OkxError::InvalidOrder(_))));
+        let move_stop = AlgoOrderRequest::MoveOrderStop(AlgoMoveOrderStop {
+            base: request_base.clone(),
+            callback_ratio: Some(Decimal::new(5, 2)),
+            callback_spread: Some(Decimal::from(100)),
+            active_px: None,
+        });
+        assert!(matches!(client.trade_order_algo(move_stop).await, Err(OkxError::InvalidOrder(_))));
+        assert!(matches!(client.trade_cancel_algos(vec![]).await, Err(OkxError::InvalidOrder(_))));
+        let requests = (0..11).map(|id| CancelAlgoRequest::new("BTC-USDT", &id.to_string())).collect();
+        assert!(matches!(client.trade_cancel_algos(requests).await, Err(OkxError::OutOfMaxOrderSize)));
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    pub async fn test_order_builder() {
+        let transport = Arc::new(MockTransport::new());
+        let client = OkxAccountClient::with_transport(crate::testnet_config(), "key", "secret", "pass", transport.clone());
+
+        let order = OrderRequestInfo::builder("BTC-USDT-SWAP", TradeMode::Cross, TradeSide::Buy, Decimal::from(1))
+            .limit(Decimal::from(20000))
+            .pos_side(PositionSide::Long)
+            .long_short_mode(true)
+            .stop_loss(Decimal::from(19000), Decimal::from(-1), Some(TpTriggerPxType::Mark))
+            .build()
+            .unwrap();
+        assert_eq!(order.inst_type(), Some(InstType::Swap));
+        let body = serde_json::to_string(&order).unwrap();
+        assert!(body.contains(r#""slTriggerPxType":"mark""#));
+
+        let result = OrderRequestInfo::builder("BTC-USDT-SWAP", TradeMode::Cross, TradeSide::Buy, Decimal::from(1))
+            .long_short_mode(true)
+            .build();
+        assert!(matches!(result, Err(OkxError::InvalidOrder(_))));
+
+        let mut order = OrderRequestInfo::new_spot_limit_order("BTC-USDT", TradeSide::Buy, Decimal::from(1), Decimal::from(20000), None, None);
+        order.px = None;
+        let result = client.trade_order(order).await;
+        assert!(matches!(result, Err(OkxError::InvalidOrder(_))));
+
+        let result = OrderRequestInfo::builder("BTC-USDT", TradeMode::Cash, TradeSide::Sell, Decimal::from(1))
+            .optimal_limit_ioc()
+            .build();
+        assert!(matches!(result, Err(OkxError::InvalidOrder(_))));
+
+        // 跨币种保证金模式下的币币全仓市价单
+        let order = OrderRequestInfo::builder("BTC-USDT", TradeMode::Cross, TradeSide::Buy, Decimal::from(100))
+            .market()
+            .tgt_ccy("quote_ccy")
+            .ban_amend()
+            .build()
+            .unwrap();
+        assert_eq!(order.inst_type(), None);
+        assert!(transport.requests().is_empty());
+    }
 }
@@ -0,0 +1,159 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::sync::Mutex;
+use std::time::Duration;
+use async_trait::async_trait;
+use http::{HeaderMap, Method, StatusCode};
+use crate::apikey::HttpConfig;
+use crate::okx_error::*;
+
+/// 发往okx的http请求
+#[derive(Clone, Debug)]
+pub struct HttpRequest {
+    pub method: Method,
+    /// 完整的请求地址，包含域名和查询参数
+    pub url: String,
+    pub headers: HeaderMap,
+    /// 请求体，GET请求为None
+    pub body: Option<String>,
+}
+
+/// okx返回的http响应
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn new(status: StatusCode, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+}
+
+/// http传输层，rest客户端通过它发送请求
+/// 默认使用ReqwestTransport，测试时可以替换为MockTransport
+#[async_trait]
+pub trait HttpTransport: Send + Sync + Debug {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+}
+
+/// 基于reqwest的传输层
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new() -> Self {
+        Self::with_client(reqwest::Client::new())
+    }
+
+    /// 按配置创建长期复用的http客户端
+    pub fn from_config(config: &HttpConfig) -> Result<Self> {
+        let mut builder = reqwest::Client::builder()
+            .pool_idle_timeout(config.pool_idle_timeout_ms.map(Duration::from_millis))
+            .tcp_keepalive(config.tcp_keepalive_ms.map(Duration::from_millis))
+            .local_address(config.local_address);
+
+        if let Some(val) = config.connect_timeout_ms {
+            builder = builder.connect_timeout(Duration::from_millis(val));
+        }
+        if let Some(val) = config.request_timeout_ms {
+            builder = builder.timeout(Duration::from_millis(val));
+        }
+        if let Some(val) = config.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(val);
+        }
+        if config.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+
+        Ok(Self::with_client(builder.build()?))
+    }
+
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl HttpTransport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let mut builder = self
+            .client
+            .request(request.method, &request.url)
+            .headers(request.headers);
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+
+        let resp = builder.send().await?;
+        let status = resp.status();
+        let headers = resp.headers().clone();
+        let body = resp.text().await?;
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+/// 内存中的传输层，按顺序返回预先设置好的响应，并记录所有收到的请求
+/// 用于在不访问okx的情况下测试策略代码
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    responses: Mutex<VecDeque<Result<HttpResponse>>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 追加一个响应
+    pub fn push_response(&self, response: HttpResponse) {
+        self.responses.lock().unwrap().push_back(Ok(response));
+    }
+
+    /// 追加一个状态码为200的json响应
+    pub fn push_json(&self, body: impl Into<String>) {
+        self.push_response(HttpResponse::new(StatusCode::OK, body));
+    }
+
+    /// 追加一个传输错误
+    pub fn push_error(&self, err: OkxError) {
+        self.responses.lock().unwrap().push_back(Err(err));
+    }
+
+    /// 已经收到的请求
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// 尚未被消费的响应数量
+    pub fn pending(&self) -> usize {
+        self.responses.lock().unwrap().len()
+    }
+}
+
+#[async_trait]
+impl HttpTransport for MockTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let url = request.url.clone();
+        self.requests.lock().unwrap().push(request);
+
+        match self.responses.lock().unwrap().pop_front() {
+            Some(val) => val,
+            None => Err(OkxError::TransportError(format!("no scripted response for {}", url))),
+        }
+    }
+}
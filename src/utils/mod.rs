@@ -1,4 +1,5 @@
 mod utils;
 pub mod request_limit;
+pub mod time_sync;
 
 pub use utils::*;
\ No newline at end of file
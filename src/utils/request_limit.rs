@@ -1,220 +1,698 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex, RwLock};
-use crate::{InstType, OkxError};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
+use fs2::FileExt;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+use crate::{APiEnum, InstType, LimitKey, LimitRule, OkxError};
 
-#[derive(Debug)]
+/// 超出限速时的处理方式
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum RateLimitMode {
+    /// 立即返回RateLimit错误
+    #[default]
+    FailFast,
+    /// 等待直到有足够的额度
+    /// max_wait: 最长等待时间，为None时一直等待，超时后返回RateLimit错误
+    Wait { max_wait: Option<Duration> },
+}
+
+/// 限速使用的时钟，单位：毫秒
+/// 测试时可以替换为ManualClock
+pub trait Clock: Send + Sync + Debug {
+    fn now_millis(&self) -> i64;
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> i64 {
+        super::get_unix()
+    }
+}
+
+/// 手动控制的时钟
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: AtomicI64,
+}
+
+impl ManualClock {
+    pub fn new(now_millis: i64) -> Self {
+        Self {
+            now: AtomicI64::new(now_millis),
+        }
+    }
+
+    pub fn set(&self, now_millis: i64) {
+        self.now.store(now_millis, Ordering::SeqCst);
+    }
+
+    pub fn advance(&self, millis: i64) {
+        self.now.fetch_add(millis, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_millis(&self) -> i64 {
+        self.now.load(Ordering::SeqCst)
+    }
+}
+
+/// 滑动窗口限速，任意window_ms毫秒内使用的权重不超过total_weight
+#[derive(Debug, Serialize, Deserialize)]
 pub struct WeightLimit {
     total_weight: u32,
-    refresh_seconds: u32,
+    window_ms: i64,
 
-    last_refresh_time: u32,
+    /// 窗口内的请求记录：(请求时间, 权重)
+    records: VecDeque<(i64, u32)>,
     used_weight: u32
 }
 
 impl WeightLimit {
     pub fn new(total_weight: u32, refresh_seconds: u32) -> Self {
+        Self::with_window_ms(total_weight, refresh_seconds as i64 * 1000)
+    }
+
+    pub fn with_window_ms(total_weight: u32, window_ms: i64) -> Self {
         Self {
             total_weight,
-            refresh_seconds,
-            last_refresh_time: 0,
+            window_ms,
+            records: VecDeque::new(),
             used_weight: 0,
         }
     }
 
-    pub fn request_once(&mut self, weight: u32) -> bool{
-        let now = (super::get_unix()/1000) as u32;
-        if now > self.refresh_seconds as u32 + self.last_refresh_time {
-            self.used_weight = 0;
-            self.last_refresh_time = now;
+    /// 移除已经滑出窗口的请求记录
+    fn evict(&mut self, now: i64) {
+        while let Some((time, weight)) = self.records.front() {
+            if *time + self.window_ms > now {
+                break;
+            }
+            self.used_weight -= weight;
+            self.records.pop_front();
         }
+    }
 
-        if self.used_weight + weight > self.total_weight {
+    pub fn request_once(&mut self, weight: u32, now: i64) -> bool{
+        if !self.check_valid(weight, now) {
             return false;
         }
 
-        self.used_weight = self.used_weight + weight;
+        self.used_weight += weight;
+        match self.records.back_mut() {
+            Some((time, val)) if *time == now => *val += weight,
+            _ => self.records.push_back((now, weight)),
+        }
         true
     }
 
-    pub fn check_valid(&mut self, weight: u32) -> bool{
-        let now = (super::get_unix()/1000) as u32;
-        if now > self.refresh_seconds as u32 + self.last_refresh_time {
-            self.used_weight = 0;
-            self.last_refresh_time = now;
+    pub fn check_valid(&mut self, weight: u32, now: i64) -> bool{
+        self.evict(now);
+        self.used_weight + weight <= self.total_weight
+    }
+
+    /// 当前窗口内剩余的权重
+    pub fn remaining(&mut self, now: i64) -> u32 {
+        self.evict(now);
+        self.total_weight.saturating_sub(self.used_weight)
+    }
+
+    /// 当前窗口内已使用的权重
+    pub fn used(&mut self, now: i64) -> u32 {
+        self.evict(now);
+        self.used_weight
+    }
+
+    /// 按调用方的规则计算的视图，不修改存储中的记录
+    /// 存储保留最长窗口内的记录，共用存储的客户端可以覆盖为不同的规则
+    fn with_rule(&mut self, rule: &LimitRule, now: i64) -> WeightLimit {
+        self.window_ms = self.window_ms.max(rule.window_ms);
+        self.evict(now);
+
+        let mut view = WeightLimit::with_window_ms(rule.capacity, rule.window_ms);
+        view.records = self.records.iter()
+            .filter(|(time, _)| *time + rule.window_ms > now)
+            .copied()
+            .collect();
+        view.used_weight = view.records.iter().map(|(_, weight)| weight).sum();
+        view
+    }
+
+    /// 需要等待多久才能有足够的额度，weight超过总额度时返回None
+    pub fn wait_time(&mut self, weight: u32, now: i64) -> Option<Duration> {
+        if weight > self.total_weight {
+            return None;
+        }
+        if self.check_valid(weight, now) {
+            return Some(Duration::ZERO);
         }
 
-        if self.used_weight + weight > self.total_weight {
-            return false;
+        // 找到最早的、滑出窗口后额度足够的请求记录
+        let mut used_weight = self.used_weight;
+        for (time, val) in &self.records {
+            used_weight -= val;
+            if used_weight + weight <= self.total_weight {
+                return Some(Duration::from_millis((*time + self.window_ms - now) as u64));
+            }
         }
 
-        true
+        Some(Duration::ZERO)
     }
 }
 
-#[derive(Debug)]
-pub struct LimitMgr {
-    data: RwLock<HashMap<u32, Arc<Mutex<WeightLimit>>>>,
-    data_with_inst_id: RwLock<HashMap<u32, HashMap<String, Arc<Mutex<WeightLimit>>>>>,
-    data_with_inst_type: RwLock<HashMap<u32, HashMap<InstType, Arc<Mutex<WeightLimit>>>>>,
-    data_with_inst_family: RwLock<HashMap<u32, HashMap<String, Arc<Mutex<WeightLimit>>>>>
+/// 限速计数的位置：接口 + 维度 + 维度值
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct LimitSlot {
+    pub api: APiEnum,
+    /// 限速的维度，期权按Instrument Family限速，可能与规则中的key不同
+    pub scope: LimitKey,
+    /// 限速的维度值，如产品id，按UserID限速时为空
+    pub key: String,
 }
 
-impl LimitMgr {
+/// 申请额度的结果
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AcquireResult {
+    Acquired,
+    /// 额度不足，需要等待的时间
+    Wait(Duration),
+    /// 申请的权重超过了总额度，等待也无法满足
+    Exceeded,
+    /// 存储正被其他进程占用，稍后重试
+    Busy,
+}
+
+/// 限速额度的存储
+/// 多个客户端使用同一个存储时共享额度，如同一个UID下的多个api key
+pub trait RateLimitStore: Send + Sync + Debug {
+    /// 按rule申请weight的额度
+    fn try_acquire(&self, slot: &LimitSlot, rule: &LimitRule, weight: u32, now: i64) -> Result<AcquireResult, OkxError>;
+
+    /// 所有维度当前窗口内已使用的权重
+    /// rule: 调用方使用的限速规则
+    fn used_weights(&self, now: i64, rule: &dyn Fn(APiEnum) -> LimitRule) -> Result<Vec<(LimitSlot, u32)>, OkxError>;
+}
+
+fn acquire_from(limit: &mut WeightLimit, rule: &LimitRule, weight: u32, now: i64) -> AcquireResult {
+    let mut view = limit.with_rule(rule, now);
+    if view.request_once(weight, now) {
+        limit.used_weight += weight;
+        match limit.records.back_mut() {
+            Some((time, val)) if *time == now => *val += weight,
+            _ => limit.records.push_back((now, weight)),
+        }
+        return AcquireResult::Acquired;
+    }
+    match view.wait_time(weight, now) {
+        Some(wait_time) => AcquireResult::Wait(wait_time),
+        None => AcquireResult::Exceeded,
+    }
+}
+
+/// 存储被占用时的重试间隔
+const BUSY_RETRY_MIN: Duration = Duration::from_millis(1);
+const BUSY_RETRY_MAX: Duration = Duration::from_millis(50);
+/// FailFast模式下存储被占用时的最多重试次数
+const BUSY_RETRY_COUNT: u32 = 5;
+
+static SHARED_STORES: Lazy<Mutex<HashMap<String, Arc<MemoryLimitStore>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 进程内的限速存储
+#[derive(Debug, Default)]
+pub struct MemoryLimitStore {
+    data: Mutex<HashMap<LimitSlot, WeightLimit>>,
+}
+
+impl MemoryLimitStore {
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 进程内按UID共享的存储，同一个uid返回同一个对象
+    pub fn shared(uid: &str) -> Arc<Self> {
+        SHARED_STORES.lock().unwrap()
+            .entry(uid.to_string())
+            .or_default()
+            .clone()
+    }
+}
+
+impl RateLimitStore for MemoryLimitStore {
+    fn try_acquire(&self, slot: &LimitSlot, rule: &LimitRule, weight: u32, now: i64) -> Result<AcquireResult, OkxError> {
+        let mut data = self.data.lock().unwrap();
+        let limit = data.entry(slot.clone())
+            .or_insert_with(|| WeightLimit::with_window_ms(rule.capacity, rule.window_ms));
+        Ok(acquire_from(limit, rule, weight, now))
+    }
+
+    fn used_weights(&self, now: i64, rule: &dyn Fn(APiEnum) -> LimitRule) -> Result<Vec<(LimitSlot, u32)>, OkxError> {
+        let mut data = self.data.lock().unwrap();
+        Ok(data.iter_mut().map(|(slot, limit)| (slot.clone(), limit.with_rule(&rule(slot.api), now).used_weight)).collect())
+    }
+}
+
+/// 使用文件锁在同一台机器的多个进程间共享的限速存储
+/// 每次申请额度时加锁读写整个文件，适合请求频率不高的场景
+#[derive(Debug)]
+pub struct FileLimitStore {
+    path: PathBuf,
+}
+
+impl FileLimitStore {
+    /// 每个UID应该使用单独的文件
+    pub fn new(path: impl Into<PathBuf>) -> Self {
         Self {
-            data: RwLock::new(HashMap::new()),
-            data_with_inst_type: RwLock::new(HashMap::new()),
-            data_with_inst_id: RwLock::new(HashMap::new()),
-            data_with_inst_family: RwLock::new(HashMap::new()),
+            path: path.into(),
         }
     }
 
-    fn get_or_add_limit(&self, api_id: u32, total_weight: u32, refresh_seconds: u32) -> Arc<Mutex<WeightLimit>>{
-        {
-            let reader = self.data.read().unwrap();
-            if let Some(val) = reader.get(&api_id) {
-                return val.clone();
+    /// 加锁后读取文件中的限速记录，修改后写回
+    /// blocking为false时不等待文件锁，被其他进程占用时返回None
+    fn update<T>(&self, now: i64, blocking: bool, f: impl FnOnce(&mut HashMap<LimitSlot, WeightLimit>) -> T) -> Result<Option<T>, OkxError> {
+        let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(&self.path)?;
+        if blocking {
+            file.lock_exclusive()?;
+        } else if let Err(err) = file.try_lock_exclusive() {
+            if err.kind() == fs2::lock_contended_error().kind() {
+                return Ok(None);
             }
+            return Err(err.into());
         }
 
-        let mut writer = self.data.write().unwrap();
-        if let Some(val) = writer.get(&api_id) {
-            return val.clone();
-        }
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        let mut data: HashMap<LimitSlot, WeightLimit> = if content.trim().is_empty() {
+            HashMap::new()
+        } else {
+            serde_json::from_str::<Vec<(LimitSlot, WeightLimit)>>(&content)?.into_iter().collect()
+        };
+
+        let result = f(&mut data);
 
-        let result = Arc::new(Mutex::new(WeightLimit::new(total_weight, refresh_seconds)));
-        writer.insert(api_id,  result.clone());
+        // 不再保存已经滑出窗口的记录
+        data.retain(|_, limit| limit.used(now) > 0);
+        let content = serde_json::to_string(&data.into_iter().collect::<Vec<_>>())?;
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(content.as_bytes())?;
+        FileExt::unlock(&file)?;
 
-        result
+        Ok(Some(result))
     }
+}
 
-    pub fn check_limit(& self, api_id: u32, weight: u32, total_weight:u32, refresh_seconds: u32) -> Result<(), OkxError> {
-        let limit_obj = self.get_or_add_limit(api_id, total_weight, refresh_seconds);
-        let mut writer = limit_obj.lock().unwrap();
-        if !writer.request_once(weight) {
-            Err(OkxError::RateLimit)
-        } else {
-            Ok(())
-        }
+impl RateLimitStore for FileLimitStore {
+    /// 在异步的check中调用，不阻塞等待文件锁
+    fn try_acquire(&self, slot: &LimitSlot, rule: &LimitRule, weight: u32, now: i64) -> Result<AcquireResult, OkxError> {
+        let result = self.update(now, false, |data| {
+            let limit = data.entry(slot.clone())
+                .or_insert_with(|| WeightLimit::with_window_ms(rule.capacity, rule.window_ms));
+            acquire_from(limit, rule, weight, now)
+        })?;
+        Ok(result.unwrap_or(AcquireResult::Busy))
     }
 
-    fn get_or_add_inst_id_limit(&self, api_id: u32, inst_id: &str, total_weight: u32, refresh_seconds: u32) -> Arc<Mutex<WeightLimit>>{
-        {
-            let reader = self.data_with_inst_id.read().unwrap();
-            if let Some(val) = reader.get(&api_id) {
-                if let Some(val) = val.get(inst_id) {
-                    return val.clone();
-                }
-            }
-        }
+    fn used_weights(&self, now: i64, rule: &dyn Fn(APiEnum) -> LimitRule) -> Result<Vec<(LimitSlot, u32)>, OkxError> {
+        let result = self.update(now, true, |data| {
+            data.iter_mut().map(|(slot, limit)| (slot.clone(), limit.with_rule(&rule(slot.api), now).used_weight)).collect()
+        })?;
+        Ok(result.unwrap_or_default())
+    }
+}
 
-        let mut writer = self.data_with_inst_id.write().unwrap();
-        if let Some(val) = writer.get_mut(&api_id) {
-            if let Some(val) = val.get_mut(inst_id) {
-                val.clone()
-            } else {
-                let result = Arc::new(Mutex::new(WeightLimit::new(total_weight, refresh_seconds)));
-                val.insert(inst_id.to_string(), result.clone());
+/// 某个限速规则的当前状态
+#[derive(Debug, Clone)]
+pub struct LimitStatus {
+    pub slot: LimitSlot,
+    pub rule: LimitRule,
+    /// 当前窗口内剩余的权重
+    pub remaining: u32,
+}
 
-                result
-            }
-        } else {
-            let result = Arc::new(Mutex::new(WeightLimit::new(total_weight, refresh_seconds)));
-            let mut data =HashMap::new();
-            data.insert(inst_id.to_string(), result.clone());
-            writer.insert(api_id,  data);
+/// 限速管理，限速模式和覆盖的规则属于每个客户端，额度保存在可共享的RateLimitStore中
+#[derive(Debug)]
+pub struct LimitMgr {
+    store: RwLock<Arc<dyn RateLimitStore>>,
+    /// 覆盖默认规则的限速规则
+    rules: RwLock<HashMap<APiEnum, LimitRule>>,
+    mode: RwLock<RateLimitMode>,
+    clock: Arc<dyn Clock>,
+}
 
-            result
-        }
+impl Default for LimitMgr {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    pub fn check_limit_with_inst_id(& self, api_id: u32, inst_id: &str, weight: u32, total_weight:u32, refresh_seconds: u32) -> Result<(), OkxError> {
-        let limit_obj = self.get_or_add_inst_id_limit(api_id, inst_id, total_weight, refresh_seconds);
-        let mut writer = limit_obj.lock().unwrap();
-        if !writer.request_once(weight) {
-            Err(OkxError::RateLimit)
-        } else {
-            Ok(())
+impl LimitMgr {
+    pub fn new() -> Self {
+        Self::with_mode(RateLimitMode::FailFast)
+    }
+
+    pub fn with_mode(mode: RateLimitMode) -> Self {
+        Self::with_clock(mode, Arc::new(SystemClock))
+    }
+
+    pub fn with_clock(mode: RateLimitMode, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            store: RwLock::new(Arc::new(MemoryLimitStore::new())),
+            rules: RwLock::new(HashMap::new()),
+            mode: RwLock::new(mode),
+            clock,
         }
     }
 
-    fn get_or_add_inst_type_limit(&self, api_id: u32, inst_type: InstType, total_weight: u32, refresh_seconds: u32) -> Arc<Mutex<WeightLimit>>{
-        {
-            let reader = self.data_with_inst_type.read().unwrap();
-            if let Some(val) = reader.get(&api_id) {
-                if let Some(val) = val.get(&inst_type) {
-                    return val.clone();
+    pub fn mode(&self) -> RateLimitMode {
+        *self.mode.read().unwrap()
+    }
+
+    pub fn set_mode(&self, mode: RateLimitMode) {
+        *self.mode.write().unwrap() = mode;
+    }
+
+    /// 替换额度的存储，已有的限速记录不会迁移
+    pub fn set_store(&self, store: Arc<dyn RateLimitStore>) {
+        *self.store.write().unwrap() = store;
+    }
+
+    fn store(&self) -> Arc<dyn RateLimitStore> {
+        self.store.read().unwrap().clone()
+    }
+
+    /// 接口当前使用的限速规则
+    pub fn rule(&self, api: APiEnum) -> LimitRule {
+        self.rules.read().unwrap().get(&api).copied().unwrap_or_else(|| api.rule())
+    }
+
+    /// 覆盖接口的默认限速规则
+    pub fn set_rule(&self, api: APiEnum, rule: LimitRule) {
+        self.rules.write().unwrap().insert(api, rule);
+    }
+
+    /// 所有已使用过的限速规则的剩余额度
+    pub fn status(&self) -> Result<Vec<LimitStatus>, OkxError> {
+        let mut result: Vec<LimitStatus> = self.store().used_weights(self.clock.now_millis(), &|api| self.rule(api))?
+            .into_iter()
+            .map(|(slot, used)| {
+                let rule = self.rule(slot.api);
+                LimitStatus {
+                    slot,
+                    rule,
+                    remaining: rule.capacity.saturating_sub(used),
                 }
-            }
-        }
+            })
+            .collect();
+        result.sort_by(|a, b| a.slot.cmp(&b.slot));
+        Ok(result)
+    }
 
-        let mut writer = self.data_with_inst_type.write().unwrap();
-        if let Some(val) = writer.get_mut(&api_id) {
-            if let Some(val) = val.get_mut(&inst_type) {
-                val.clone()
-            } else {
-                let result = Arc::new(Mutex::new(WeightLimit::new(total_weight, refresh_seconds)));
-                val.insert(inst_type, result.clone());
+    /// 接口在某个维度下的剩余额度，按UserID限速时key为空
+    pub fn remaining(&self, api: APiEnum, scope: LimitKey, key: &str) -> Result<u32, OkxError> {
+        let status = self.status()?;
+        let remaining = status.iter()
+            .find(|item| item.slot.api == api && item.slot.scope == scope && item.slot.key == key)
+            .map(|item| item.remaining);
+        Ok(remaining.unwrap_or_else(|| self.rule(api).capacity))
+    }
+
+    /// 按当前的限速模式申请额度
+    /// count: 本次请求包含的数量，如批量下单的订单数，使用的权重为count * rule.weight
+    async fn check(&self, api: APiEnum, scope: LimitKey, key: &str, count: u32) -> Result<(), OkxError> {
+        self.check_with_mode(api, scope, key, count, self.mode()).await
+    }
 
-                result
+    async fn check_with_mode(&self, api: APiEnum, scope: LimitKey, key: &str, count: u32, mode: RateLimitMode) -> Result<(), OkxError> {
+        let rule = self.rule(api);
+        let weight = count * rule.weight;
+        let slot = LimitSlot {
+            api,
+            scope,
+            key: key.to_string(),
+        };
+        let store = self.store();
+
+        let deadline = match mode {
+            RateLimitMode::FailFast => None,
+            RateLimitMode::Wait { max_wait } => Some(max_wait.map(|val| Instant::now() + val)),
+        };
+
+        let mut busy_wait = BUSY_RETRY_MIN;
+        let mut busy_count = 0;
+        loop {
+            let wait_time = match store.try_acquire(&slot, &rule, weight, self.clock.now_millis())? {
+                AcquireResult::Acquired => return Ok(()),
+                AcquireResult::Wait(wait_time) => wait_time,
+                AcquireResult::Exceeded => return Err(OkxError::RateLimit),
+                // 存储被占用时退避重试，FailFast模式下只重试有限次数，Wait模式下不超过max_wait
+                AcquireResult::Busy => {
+                    let timeout = match deadline {
+                        None => busy_count >= BUSY_RETRY_COUNT,
+                        Some(Some(deadline)) => Instant::now() + busy_wait > deadline,
+                        Some(None) => false,
+                    };
+                    if timeout {
+                        return Err(OkxError::LimitStoreBusy);
+                    }
+                    busy_count += 1;
+                    tokio::time::sleep(busy_wait).await;
+                    busy_wait = (busy_wait * 2).min(BUSY_RETRY_MAX);
+                    continue;
+                }
+            };
+
+            let deadline = match deadline {
+                Some(deadline) => deadline,
+                None => return Err(OkxError::RateLimit),
+            };
+            if let Some(deadline) = deadline {
+                if Instant::now() + wait_time > deadline {
+                    return Err(OkxError::RateLimit);
+                }
             }
-        } else {
-            let result = Arc::new(Mutex::new(WeightLimit::new(total_weight, refresh_seconds)));
-            let mut data =HashMap::new();
-            data.insert(inst_type, result.clone());
-            writer.insert(api_id,  data);
 
-            result
+            tokio::time::sleep(wait_time).await;
         }
     }
 
-    pub fn check_limit_with_inst_type(& self, api_id: u32, inst_type: InstType, weight: u32, total_weight:u32, refresh_seconds: u32) -> Result<(), OkxError> {
-        let limit_obj = self.get_or_add_inst_type_limit(api_id, inst_type, total_weight, refresh_seconds);
-        let mut writer = limit_obj.lock().unwrap();
-        if !writer.request_once(weight) {
-            Err(OkxError::RateLimit)
-        } else {
-            Ok(())
+    /// 按UserID限速，公共接口为IP
+    pub async fn check_limit(&self, api: APiEnum, count: u32) -> Result<(), OkxError> {
+        self.check(api, LimitKey::UserId, "", count).await
+    }
+
+    /// 按UserID限速，不论限速模式都等待到有额度为止，用于分页下载等批量任务
+    pub async fn wait_limit(&self, api: APiEnum, count: u32) -> Result<(), OkxError> {
+        self.check_with_mode(api, LimitKey::UserId, "", count, RateLimitMode::Wait { max_wait: None }).await
+    }
+
+    /// 按UserID + Instrument ID限速
+    pub async fn check_limit_with_inst_id(&self, api: APiEnum, inst_id: &str, count: u32) -> Result<(), OkxError> {
+        self.check(api, LimitKey::InstId, inst_id, count).await
+    }
+
+    /// 按UserID + Instrument Type限速
+    pub async fn check_limit_with_inst_type(&self, api: APiEnum, inst_type: InstType, count: u32) -> Result<(), OkxError> {
+        let inst_type: String = inst_type.into();
+        self.check(api, LimitKey::InstType, &inst_type, count).await
+    }
+
+    /// 按UserID + Instrument Family限速
+    pub async fn check_limit_with_inst_family(&self, api: APiEnum, inst_family: &str, count: u32) -> Result<(), OkxError> {
+        self.check(api, LimitKey::InstFamily, inst_family, count).await
+    }
+
+    /// 按UserID + Currency限速
+    pub async fn check_limit_with_ccy(&self, api: APiEnum, ccy: &str, count: u32) -> Result<(), OkxError> {
+        self.check(api, LimitKey::Ccy, ccy, count).await
+    }
+
+    /// 交易类接口的限速：期权按UserID + Instrument Family，其他按UserID + Instrument ID
+    pub async fn check_limit_with_instrument(&self, api: APiEnum, inst_id: &str, count: u32) -> Result<(), OkxError> {
+        match option_inst_family(inst_id) {
+            Some(inst_family) => self.check_limit_with_inst_family(api, inst_family, count).await,
+            None => self.check_limit_with_inst_id(api, inst_id, count).await,
         }
     }
+}
 
-    fn get_or_add_inst_family_limit(&self, api_id: u32, inst_family: &str, total_weight: u32, refresh_seconds: u32) -> Arc<Mutex<WeightLimit>>{
-        {
-            let reader = self.data_with_inst_family.read().unwrap();
-            if let Some(val) = reader.get(&api_id) {
-                if let Some(val) = val.get(inst_family) {
-                    return val.clone();
-                }
-            }
+/// 期权产品id对应的产品族，如BTC-USD-230630-30000-C对应BTC-USD，非期权返回None
+pub fn option_inst_family(inst_id: &str) -> Option<&str> {
+    let parts: Vec<&str> = inst_id.split('-').collect();
+    match parts.as_slice() {
+        [base, quote, _, _, "C" | "P"] => Some(&inst_id[..base.len() + quote.len() + 1]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use crate::{APiEnum, LimitKey, LimitRule, OkxError};
+    use super::{option_inst_family, FileLimitStore, LimitMgr, ManualClock, MemoryLimitStore, RateLimitStore, RateLimitMode, WeightLimit};
+
+    #[test]
+    pub fn test_sliding_window() {
+        let mut limit = WeightLimit::new(10, 2);
+
+        // 窗口边界两侧的突发请求不能超过总额度
+        assert!(limit.request_once(6, 1_999));
+        assert!(limit.request_once(4, 2_001));
+        assert!(!limit.request_once(1, 2_500));
+        assert_eq!(limit.wait_time(1, 2_500), Some(Duration::from_millis(1_499)));
+
+        // 第一批请求滑出窗口后，只释放它自己的额度
+        assert_eq!(limit.remaining(3_999), 6);
+        assert!(limit.request_once(6, 3_999));
+        assert!(!limit.request_once(1, 3_999));
+        assert_eq!(limit.wait_time(4, 3_999), Some(Duration::from_millis(2)));
+        assert_eq!(limit.wait_time(5, 3_999), Some(Duration::from_millis(2_000)));
+        assert_eq!(limit.remaining(4_001), 4);
+
+        assert_eq!(limit.wait_time(11, 4_001), None);
+    }
+
+    #[tokio::test]
+    pub async fn test_limit_mgr_with_manual_clock() {
+        let clock = Arc::new(ManualClock::new(1_000_000));
+        let mgr = LimitMgr::with_clock(RateLimitMode::FailFast, clock.clone());
+        mgr.set_rule(APiEnum::TradePlaceOrder, LimitRule::new(5, 2000, LimitKey::InstId));
+
+        for _ in 0..5 {
+            mgr.check_limit_with_inst_id(APiEnum::TradePlaceOrder, "BTC-USDT", 1).await.unwrap();
         }
+        assert!(matches!(mgr.check_limit_with_inst_id(APiEnum::TradePlaceOrder, "BTC-USDT", 1).await, Err(OkxError::RateLimit)));
+        // 不同产品互不影响
+        mgr.check_limit_with_inst_id(APiEnum::TradePlaceOrder, "ETH-USDT", 1).await.unwrap();
+        assert_eq!(mgr.remaining(APiEnum::TradePlaceOrder, LimitKey::InstId, "ETH-USDT").unwrap(), 4);
+        assert_eq!(mgr.remaining(APiEnum::TradeAmendOrder, LimitKey::InstId, "ETH-USDT").unwrap(), APiEnum::TradeAmendOrder.rule().capacity);
+
+        clock.advance(1_999);
+        assert!(mgr.check_limit_with_inst_id(APiEnum::TradePlaceOrder, "BTC-USDT", 1).await.is_err());
+        clock.advance(1);
+        mgr.check_limit_with_inst_id(APiEnum::TradePlaceOrder, "BTC-USDT", 5).await.unwrap();
+
+        let status = mgr.status().unwrap();
+        assert_eq!(status.len(), 2);
+        assert_eq!((status[0].slot.key.as_str(), status[0].remaining), ("BTC-USDT", 0));
+        assert_eq!(status[1].remaining, 5);
+
+        // 等待模式下，超过最长等待时间仍然返回错误
+        mgr.set_mode(RateLimitMode::Wait { max_wait: Some(Duration::from_millis(10)) });
+        assert!(matches!(mgr.check_limit_with_inst_id(APiEnum::TradePlaceOrder, "BTC-USDT", 1).await, Err(OkxError::RateLimit)));
+    }
+
+    #[tokio::test]
+    pub async fn test_option_limit_by_inst_family() {
+        assert_eq!(option_inst_family("BTC-USD-230630-30000-C"), Some("BTC-USD"));
+        assert_eq!(option_inst_family("ETH-USD-230630-1800-P"), Some("ETH-USD"));
+        assert_eq!(option_inst_family("BTC-USD-230630"), None);
+        assert_eq!(option_inst_family("BTC-USDT-SWAP"), None);
+
+        let mgr = LimitMgr::with_clock(RateLimitMode::FailFast, Arc::new(ManualClock::new(1_000_000)));
+        mgr.set_rule(APiEnum::TradePlaceOrder, LimitRule::new(2, 2000, LimitKey::InstId));
+
+        // 同一产品族的不同期权共用额度
+        mgr.check_limit_with_instrument(APiEnum::TradePlaceOrder, "BTC-USD-230630-30000-C", 1).await.unwrap();
+        mgr.check_limit_with_instrument(APiEnum::TradePlaceOrder, "BTC-USD-230630-32000-P", 1).await.unwrap();
+        assert!(mgr.check_limit_with_instrument(APiEnum::TradePlaceOrder, "BTC-USD-230707-30000-C", 1).await.is_err());
+        assert_eq!(mgr.remaining(APiEnum::TradePlaceOrder, LimitKey::InstFamily, "BTC-USD").unwrap(), 0);
+
+        // 非期权仍按产品id限速
+        mgr.check_limit_with_instrument(APiEnum::TradePlaceOrder, "BTC-USD-SWAP", 1).await.unwrap();
+        assert_eq!(mgr.remaining(APiEnum::TradePlaceOrder, LimitKey::InstId, "BTC-USD-SWAP").unwrap(), 1);
+    }
 
-        let mut writer = self.data_with_inst_family.write().unwrap();
-        if let Some(val) = writer.get_mut(&api_id) {
-            if let Some(val) = val.get_mut(inst_family) {
-                val.clone()
-            } else {
-                let result = Arc::new(Mutex::new(WeightLimit::new(total_weight, refresh_seconds)));
-                val.insert(inst_family.to_string(), result.clone());
+    #[tokio::test]
+    pub async fn test_shared_store() {
+        let clock = Arc::new(ManualClock::new(1_000_000));
+        let path = std::env::temp_dir().join(format!("okx_limit_{}.json", std::process::id()));
+        let stores: Vec<Arc<dyn RateLimitStore>> = vec![
+            MemoryLimitStore::shared("test_shared_store"),
+            Arc::new(FileLimitStore::new(&path)),
+        ];
 
-                result
+        for store in stores {
+            // 两个客户端共用额度，限速模式各自独立
+            let mgr1 = LimitMgr::with_clock(RateLimitMode::FailFast, clock.clone());
+            let wait_mode = RateLimitMode::Wait { max_wait: Some(Duration::from_millis(10)) };
+            let mgr2 = LimitMgr::with_clock(wait_mode, clock.clone());
+            mgr1.set_store(store.clone());
+            mgr2.set_store(store);
+
+            for _ in 0..2 {
+                mgr1.check_limit(APiEnum::AccountConfig, 1).await.unwrap();
+                mgr2.check_limit(APiEnum::AccountConfig, 1).await.unwrap();
             }
-        } else {
-            let result = Arc::new(Mutex::new(WeightLimit::new(total_weight, refresh_seconds)));
-            let mut data =HashMap::new();
-            data.insert(inst_family.to_string(), result.clone());
-            writer.insert(api_id,  data);
+            mgr1.check_limit(APiEnum::AccountConfig, 1).await.unwrap();
+            assert!(mgr1.check_limit(APiEnum::AccountConfig, 1).await.is_err());
+            assert!(mgr2.check_limit(APiEnum::AccountConfig, 1).await.is_err());
+            assert_eq!(mgr2.remaining(APiEnum::AccountConfig, LimitKey::UserId, "").unwrap(), 0);
+            assert_eq!(mgr1.mode(), RateLimitMode::FailFast);
 
-            result
+            clock.advance(2_000);
         }
+        std::fs::remove_file(&path).unwrap();
     }
 
-    pub fn check_limit_with_inst_family(&self, api_id: u32, inst_family: &str, weight: u32, total_weight:u32, refresh_seconds: u32) -> Result<(), OkxError> {
-        let limit_obj = self.get_or_add_inst_family_limit(api_id, inst_family, total_weight, refresh_seconds);
-        let mut writer = limit_obj.lock().unwrap();
-        if !writer.request_once(weight) {
-            Err(OkxError::RateLimit)
-        } else {
-            Ok(())
+    #[tokio::test]
+    pub async fn test_shared_store_rules() {
+        let clock = Arc::new(ManualClock::new(1_000_000));
+        let store = Arc::new(MemoryLimitStore::new());
+        // mgr1覆盖为10s窗口，mgr2使用默认的2s窗口
+        let mgr1 = LimitMgr::with_clock(RateLimitMode::FailFast, clock.clone());
+        mgr1.set_rule(APiEnum::AccountConfig, LimitRule::new(5, 10_000, LimitKey::UserId));
+        mgr1.set_store(store.clone());
+        let mgr2 = LimitMgr::with_clock(RateLimitMode::FailFast, clock.clone());
+        mgr2.set_store(store);
+
+        for _ in 0..5 {
+            mgr1.check_limit(APiEnum::AccountConfig, 1).await.unwrap();
         }
+        clock.advance(3_000);
+        // mgr2的窗口内没有记录，但不能清除mgr1窗口内的记录
+        mgr2.check_limit(APiEnum::AccountConfig, 1).await.unwrap();
+        assert!(mgr1.check_limit(APiEnum::AccountConfig, 1).await.is_err());
+        assert_eq!(mgr1.remaining(APiEnum::AccountConfig, LimitKey::UserId, "").unwrap(), 0);
+        assert_eq!(mgr2.remaining(APiEnum::AccountConfig, LimitKey::UserId, "").unwrap(), 4);
+    }
+
+    #[tokio::test]
+    pub async fn test_file_store_locked() {
+        let path = std::env::temp_dir().join(format!("okx_limit_locked_{}.json", std::process::id()));
+        let holder = std::fs::OpenOptions::new().write(true).create(true).truncate(false).open(&path).unwrap();
+        fs2::FileExt::lock_exclusive(&holder).unwrap();
+
+        // 文件被其他进程占用时异步等待，不阻塞工作线程
+        let mgr = Arc::new(LimitMgr::with_mode(RateLimitMode::Wait { max_wait: None }));
+        mgr.set_store(Arc::new(FileLimitStore::new(&path)));
+        let task = tokio::spawn({
+            let mgr = mgr.clone();
+            async move { mgr.check_limit(APiEnum::AccountConfig, 1).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!task.is_finished());
+
+        // FailFast模式和超过max_wait时不会一直等待
+        let fail_fast = LimitMgr::new();
+        fail_fast.set_store(Arc::new(FileLimitStore::new(&path)));
+        assert!(matches!(fail_fast.check_limit(APiEnum::AccountConfig, 1).await, Err(OkxError::LimitStoreBusy)));
+        let wait = LimitMgr::with_mode(RateLimitMode::Wait { max_wait: Some(Duration::from_millis(10)) });
+        wait.set_store(Arc::new(FileLimitStore::new(&path)));
+        assert!(matches!(wait.check_limit(APiEnum::AccountConfig, 1).await, Err(OkxError::LimitStoreBusy)));
+
+        fs2::FileExt::unlock(&holder).unwrap();
+        task.await.unwrap().unwrap();
+        assert_eq!(mgr.remaining(APiEnum::AccountConfig, LimitKey::UserId, "").unwrap(), 4);
+        std::fs::remove_file(&path).unwrap();
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,50 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+use chrono::{TimeZone, Utc};
+
+/// 本地时钟与okx服务器时间的偏差，用于签名时的时间戳
+/// okx会拒绝与服务器时间相差30秒以上的请求(50102)
+#[derive(Debug, Default)]
+pub struct TimeSync {
+    /// 服务器时间 - 本地时间，单位：毫秒
+    offset_ms: AtomicI64,
+}
+
+impl TimeSync {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn offset_ms(&self) -> i64 {
+        self.offset_ms.load(Ordering::Relaxed)
+    }
+
+    pub fn set_offset_ms(&self, offset_ms: i64) {
+        self.offset_ms.store(offset_ms, Ordering::Relaxed);
+    }
+
+    /// 按请求发出和收到响应的中间时刻估算偏差，返回新的偏差
+    /// send_time、recv_time为本地时间，server_time为服务器返回的时间，单位：毫秒
+    pub fn update(&self, send_time: i64, recv_time: i64, server_time: i64) -> i64 {
+        let offset_ms = server_time - (send_time + recv_time) / 2;
+        self.set_offset_ms(offset_ms);
+        offset_ms
+    }
+
+    /// 校正后的当前时间，单位：毫秒
+    pub fn now_millis(&self) -> i64 {
+        super::get_unix() + self.offset_ms()
+    }
+
+    /// rest签名使用的ISO格式时间戳，如 2020-12-08T09:08:57.715Z
+    pub fn rest_timestamp(&self) -> String {
+        Utc.timestamp_millis_opt(self.now_millis())
+            .unwrap()
+            .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+            .to_string()
+    }
+
+    /// websocket登录使用的时间戳，单位：秒
+    pub fn websocket_timestamp(&self) -> i64 {
+        self.now_millis() / 1000
+    }
+}
@@ -2,6 +2,7 @@ use chrono::format::DelayedFormat;
 use chrono::format::StrftimeItems;
 use chrono::prelude::*;
 use serde::{de, Deserialize, Deserializer, Serializer};
+use serde::de::DeserializeOwned;
 use std::fmt::Display;
 use std::str::FromStr;
 
@@ -53,7 +54,8 @@ where
     T::Err: Display,
     D: Deserializer<'de>,
 {
-    let s = String::deserialize(deserializer)?;
+    // 兼容to_opt_str序列化出的null
+    let s = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
     if s.is_empty() {
         return Ok(None);
     }
@@ -61,6 +63,20 @@ where
     Ok(Some(T::from_str(&s).map_err(de::Error::custom)?))
 }
 
+/// 按serde的枚举定义反序列化，空字符串为None
+pub fn from_opt_enum<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: DeserializeOwned,
+    D: Deserializer<'de>,
+{
+    let s = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+    if s.is_empty() {
+        return Ok(None);
+    }
+
+    T::deserialize(de::value::StringDeserializer::<D::Error>::new(s)).map(Some)
+}
+
 pub fn to_opt_str<T, S>(val: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
     where
         T: Display,
@@ -10,7 +10,9 @@ use ring::hmac;
 use serde::{Deserialize, Serialize};
 use crate::{ExecuteType, OrderState, OrderType, PositionSide, StopMode, TpTriggerPxType, TradeMode, TradeSide};
 use crate::restful::InstType;
-use crate::utils::{from_str, to_str};
+use rust_decimal::Decimal;
+use crate::utils::{from_opt_str, from_str, to_opt_str, to_str};
+use crate::utils::time_sync::TimeSync;
 
 use crate::websocket::conn::{EventResponse, Handler, WebsocketConn};
 
@@ -22,10 +24,16 @@ pub struct AccountWebsocket {
     handler: RwLock<Arc<BTreeMap<String, Arc<Box<dyn AccountHandler>>>>>,
     is_account_subscribed: AtomicBool,
     order_subscribed: Mutex<Vec<InstType>>,
+    time_sync: Arc<TimeSync>,
 }
 
 impl AccountWebsocket {
     pub async fn start(api_key: &str, secret_key: &str, passphrase: &str, url: &str) -> Arc<Self> {
+        Self::start_with_time_sync(api_key, secret_key, passphrase, url, Arc::new(TimeSync::new())).await
+    }
+
+    /// 登录签名时使用time_sync校正后的时间
+    pub async fn start_with_time_sync(api_key: &str, secret_key: &str, passphrase: &str, url: &str, time_sync: Arc<TimeSync>) -> Arc<Self> {
         let result = Arc::new(Self {
             api_key: api_key.to_string(),
             secret_key: secret_key.to_string(),
@@ -34,6 +42,7 @@ impl AccountWebsocket {
             handler: RwLock::new(Arc::new(BTreeMap::new())),
             is_account_subscribed: Default::default(),
             order_subscribed: Mutex::new(vec![]),
+            time_sync,
         });
 
         let week = Arc::downgrade(&result);
@@ -77,7 +86,7 @@ impl AccountWebsocket {
     }
 
     fn get_timestamp(&self) -> i64 {
-        chrono::Utc::now().timestamp()
+        self.time_sync.websocket_timestamp()
     }
 
     fn sign(&self, time_stamp: String, method: http::Method, data: &str) -> String {
@@ -323,69 +332,75 @@ pub struct AccountEventArg {
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct AccountAssetItemEvent {
-    #[serde(rename = "availBal")]
-    pub avail_bal: String,
-    #[serde(rename = "availEq")]
-    pub avail_eq: String,
+    #[serde(rename = "availBal", serialize_with="to_str",deserialize_with="from_str")]
+    pub avail_bal: Decimal,
+    #[serde(rename = "availEq", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub avail_eq: Option<Decimal>,
     pub ccy: String,
-    #[serde(rename = "cashBal")]
-    pub cash_bal: String,
+    #[serde(rename = "cashBal", serialize_with="to_str",deserialize_with="from_str")]
+    pub cash_bal: Decimal,
     #[serde(rename = "uTime", serialize_with="to_str",deserialize_with="from_str")]
     pub u_time: i64,
-    #[serde(rename = "disEq")]
-    pub dis_eq: String,
-    pub eq: String,
-    #[serde(rename = "eqUsd")]
-    pub eq_usd: String,
-    #[serde(rename = "frozenBal")]
-    pub frozen_bal: String,
-    pub interest: String,
-    #[serde(rename = "isoEq")]
-    pub iso_eq: String,
-    pub liab: String,
-    #[serde(rename = "maxLoan")]
-    pub max_loan: String,
-    #[serde(rename = "mgnRatio")]
-    pub mgn_ratio: String,
-    #[serde(rename = "notionalLever")]
-    pub notional_lever: String,
-    #[serde(rename = "ordFrozen")]
-    pub ord_frozen: String,
-    pub upl: String,
-    #[serde(rename = "uplLiab")]
-    pub upl_liab: String,
-    #[serde(rename = "crossLiab")]
-    pub cross_liab: String,
-    #[serde(rename = "isoLiab")]
-    pub iso_liab: String,
-    #[serde(rename = "coinUsdPrice")]
-    pub coin_usd_price: String,
-    #[serde(rename = "stgyEq")]
-    pub stgy_eq: String,
-    #[serde(rename = "spotInUseAmt")]
-    pub spot_in_use_amt: String,
-    #[serde(rename = "isoUpl")]
-    pub iso_upl: String,
+    #[serde(rename = "disEq", serialize_with="to_str",deserialize_with="from_str")]
+    pub dis_eq: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub eq: Decimal,
+    #[serde(rename = "eqUsd", serialize_with="to_str",deserialize_with="from_str")]
+    pub eq_usd: Decimal,
+    #[serde(rename = "frozenBal", serialize_with="to_str",deserialize_with="from_str")]
+    pub frozen_bal: Decimal,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub interest: Option<Decimal>,
+    #[serde(rename = "isoEq", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub iso_eq: Option<Decimal>,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub liab: Option<Decimal>,
+    #[serde(rename = "maxLoan", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub max_loan: Option<Decimal>,
+    #[serde(rename = "mgnRatio", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub mgn_ratio: Option<Decimal>,
+    #[serde(rename = "notionalLever", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub notional_lever: Option<Decimal>,
+    #[serde(rename = "ordFrozen", serialize_with="to_str",deserialize_with="from_str")]
+    pub ord_frozen: Decimal,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub upl: Option<Decimal>,
+    #[serde(rename = "uplLiab", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub upl_liab: Option<Decimal>,
+    #[serde(rename = "crossLiab", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub cross_liab: Option<Decimal>,
+    #[serde(rename = "isoLiab", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub iso_liab: Option<Decimal>,
+    #[serde(rename = "coinUsdPrice", serialize_with="to_str",deserialize_with="from_str")]
+    pub coin_usd_price: Decimal,
+    #[serde(rename = "stgyEq", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub stgy_eq: Option<Decimal>,
+    #[serde(rename = "spotInUseAmt", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub spot_in_use_amt: Option<Decimal>,
+    #[serde(rename = "isoUpl", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub iso_upl: Option<Decimal>,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct AccountEvent {
     #[serde(rename = "uTime", serialize_with="to_str",deserialize_with="from_str")]
     pub u_time: i64,
-    #[serde(rename = "totalEq")]
-    pub total_eq: String,
-    #[serde(rename = "isoEq")]
-    pub iso_eq: String,
-    #[serde(rename = "adjEq")]
-    pub adj_eq: String,
-    #[serde(rename = "ordFroz")]
-    pub ord_froz: String,
-    pub imr: String,
-    pub mmr: String,
-    #[serde(rename = "notionalUsd")]
-    pub notional_usd: String,
-    #[serde(rename = "mgnRatio")]
-    pub mgn_ratio: String,
+    #[serde(rename = "totalEq", serialize_with="to_str",deserialize_with="from_str")]
+    pub total_eq: Decimal,
+    #[serde(rename = "isoEq", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub iso_eq: Option<Decimal>,
+    #[serde(rename = "adjEq", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub adj_eq: Option<Decimal>,
+    #[serde(rename = "ordFroz", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub ord_froz: Option<Decimal>,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub imr: Option<Decimal>,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub mmr: Option<Decimal>,
+    #[serde(rename = "notionalUsd", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub notional_usd: Option<Decimal>,
+    #[serde(rename = "mgnRatio", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub mgn_ratio: Option<Decimal>,
     pub details: Vec<AccountAssetItemEvent>,
 }
 
@@ -419,12 +434,14 @@ pub struct OrderEvent {
     /// 订单标签
     pub tag: String,
     /// 委托价格
-    pub px: String,
+    #[serde(serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub px: Option<Decimal>,
     /// 原始委托数量，币币/币币杠杆，以币为单位；交割/永续/期权 ，以张为单位
-    pub sz: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub sz: Decimal,
     /// 委托单预估美元价值
-    #[serde(rename = "notionalUsd")]
-    pub notional_usd: String,
+    #[serde(rename = "notionalUsd", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub notional_usd: Option<Decimal>,
     /// 订单类型
     #[serde(rename = "ordType")]
     pub ord_type: OrderType,
@@ -446,27 +463,27 @@ pub struct OrderEvent {
     #[serde(rename = "tgtCcy")]
     pub tgt_ccy: String,
     /// 最新成交价格
-    #[serde(rename = "fillPx")]
-    pub fill_px: String,
+    #[serde(rename = "fillPx", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub fill_px: Option<Decimal>,
     /// 最新成交ID
     #[serde(rename = "tradeId", serialize_with="to_str",deserialize_with="from_str")]
     pub trade_id: i64,
     /// 最新成交数量
     /// 对于币币和杠杆，单位为交易货币，如 BTC-USDT, 单位为 BTC；对于市价单，无论tgtCcy是base_ccy，还是quote_ccy，单位均为交易货币；
     /// 对于交割、永续以及期权，单位为张。
-    #[serde(rename = "fillSz")]
-    pub fill_sz: String,
+    #[serde(rename = "fillSz", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub fill_sz: Option<Decimal>,
     /// 最新成交收益，适用于有成交的平仓订单。其他情况均为0。
-    #[serde(rename = "fillPnl")]
-    pub fill_pnl: String,
+    #[serde(rename = "fillPnl", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub fill_pnl: Option<Decimal>,
     /// 最新成交时间
     #[serde(rename = "fillTime", serialize_with="to_str",deserialize_with="from_str")]
     pub fill_time: i64,
     /// 最新一笔成交的手续费金额或者返佣金额：
     /// 手续费扣除 为 ‘负数’，如 -0.01 ；
     /// 手续费返佣 为 ‘正数’，如 0.01
-    #[serde(rename = "fillFee")]
-    pub fill_fee: String,
+    #[serde(rename = "fillFee", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub fill_fee: Option<Decimal>,
     /// 最新一笔成交的手续费币种或者返佣币种。
     /// 如果fillFee小于0，为手续费币种；如果fillFee大于等于0，为返佣币种
     #[serde(rename = "fillFeeCcy")]
@@ -477,14 +494,14 @@ pub struct OrderEvent {
     /// 累计成交数量
     /// 对于币币和杠杆，单位为交易货币，如 BTC-USDT, 单位为 BTC；对于市价单，无论tgtCcy是base_ccy，还是quote_ccy，单位均为交易货币；
     /// 对于交割、永续以及期权，单位为张。
-    #[serde(rename = "accFillSz")]
-    pub acc_fill_sz: String,
+    #[serde(rename = "accFillSz", serialize_with="to_str",deserialize_with="from_str")]
+    pub acc_fill_sz: Decimal,
     /// 委托单已成交的美元价值
-    #[serde(rename = "fillNotionalUsd")]
-    pub fill_notional_usd: String,
+    #[serde(rename = "fillNotionalUsd", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub fill_notional_usd: Option<Decimal>,
     /// 成交均价，如果成交数量为0，该字段也为0
-    #[serde(rename = "avgPx")]
-    pub avg_px: String,
+    #[serde(rename = "avgPx", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub avg_px: Option<Decimal>,
     /// 订单状态
     pub state: OrderState,
     /// 杠杆倍数，0.01到125之间的数值，仅适用于 币币杠杆/交割/永续
@@ -493,23 +510,23 @@ pub struct OrderEvent {
     #[serde(rename = "attachAlgoClOrdId")]
     pub attach_algo_cl_ord_id: String,
     /// 止盈触发价
-    #[serde(rename = "tpTriggerPx")]
-    pub tp_trigger_px: String,
+    #[serde(rename = "tpTriggerPx", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub tp_trigger_px: Option<Decimal>,
     /// 止盈触发价类型
     #[serde(rename = "tpTriggerPxType")]
     pub tp_trigger_px_type: TpTriggerPxType,
     /// 止盈委托价，止盈委托价格为-1时，执行市价止盈
-    #[serde(rename = "tpOrdPx")]
-    pub tp_ord_px: String,
+    #[serde(rename = "tpOrdPx", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub tp_ord_px: Option<Decimal>,
     /// 止损触发价
-    #[serde(rename = "slTriggerPx")]
-    pub sl_trigger_px: String,
+    #[serde(rename = "slTriggerPx", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub sl_trigger_px: Option<Decimal>,
     /// 止损触发价类型
     #[serde(rename = "slTriggerPxType")]
     pub sl_trigger_px_type: TpTriggerPxType,
     /// 止损委托价，止损委托价格为-1时，执行市价止损
-    #[serde(rename = "slOrdPx")]
-    pub sl_ord_px: String,
+    #[serde(rename = "slOrdPx", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub sl_ord_px: Option<Decimal>,
     /// 自成交保护ID
     /// 如果自成交保护不适用则返回""
     #[serde(rename = "stpId")]
@@ -526,14 +543,17 @@ pub struct OrderEvent {
     /// 订单交易累计的手续费与返佣
     /// 对于币币和杠杆，为订单交易累计的手续费，平台向用户收取的交易手续费，为负数。如： -0.01
     /// 对于交割、永续和期权，为订单交易累计的手续费和返佣
-    pub fee: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub fee: Decimal,
     /// 返佣金币种 ，如果没有返佣金，该字段为“”
     #[serde(rename = "rebateCcy")]
     pub rebate_ccy: String,
     /// 返佣累计金额，仅适用于币币和杠杆，平台向达到指定lv交易等级的用户支付的挂单奖励（返佣），如果没有返佣金，该字段为“”
-    pub rebate: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub rebate: Decimal,
     /// 收益，适用于有成交的平仓订单，其他情况均为0
-    pub pnl: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub pnl: Decimal,
     /// 订单来源
     /// 13:策略委托单触发后的生成的限价单
     pub source: String,
@@ -9,7 +9,8 @@ use crate::restful::InstType;
 use crate::{Instrument, Trade};
 use crate::websocket::{EventResponse, Handler, WebsocketConn};
 use crate::websocket::order_book_merge::{OrderBookMergeMgr};
-use crate::utils::{from_str, to_str};
+use rust_decimal::Decimal;
+use crate::utils::{from_opt_str, from_str, to_opt_str, to_str};
 
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub enum OrderBookSize {
@@ -570,27 +571,32 @@ pub struct TickerEvent {
     pub inst_type: InstType,
     #[serde(rename = "instId")]
     pub inst_id: String,
-    pub last: String,
-    #[serde(rename = "lastSz")]
-    pub last_sz: String,
-    #[serde(rename = "askPx")]
-    pub ask_px: String,
-    #[serde(rename = "askSz")]
-    pub ask_sz: String,
-    #[serde(rename = "bidPx")]
-    pub bid_px: String,
-    #[serde(rename = "bidSz")]
-    pub bid_sz: String,
-    pub open24h: String,
-    pub high24h: String,
-    pub low24h: String,
-    #[serde(rename = "volCcy24h")]
-    pub vol_ccy24h: String,
-    pub vol24h: String,
-    #[serde(rename = "sodUtc0")]
-    pub sod_utc0: String,
-    #[serde(rename = "sodUtc8")]
-    pub sod_utc8: String,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub last: Decimal,
+    #[serde(rename = "lastSz", serialize_with="to_str",deserialize_with="from_str")]
+    pub last_sz: Decimal,
+    #[serde(rename = "askPx", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub ask_px: Option<Decimal>,
+    #[serde(rename = "askSz", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub ask_sz: Option<Decimal>,
+    #[serde(rename = "bidPx", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub bid_px: Option<Decimal>,
+    #[serde(rename = "bidSz", serialize_with="to_opt_str",deserialize_with="from_opt_str")]
+    pub bid_sz: Option<Decimal>,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub open24h: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub high24h: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub low24h: Decimal,
+    #[serde(rename = "volCcy24h", serialize_with="to_str",deserialize_with="from_str")]
+    pub vol_ccy24h: Decimal,
+    #[serde(serialize_with="to_str",deserialize_with="from_str")]
+    pub vol24h: Decimal,
+    #[serde(rename = "sodUtc0", serialize_with="to_str",deserialize_with="from_str")]
+    pub sod_utc0: Decimal,
+    #[serde(rename = "sodUtc8", serialize_with="to_str",deserialize_with="from_str")]
+    pub sod_utc8: Decimal,
     #[serde(serialize_with="to_str",deserialize_with="from_str")]
     pub ts: i64,
 }